@@ -0,0 +1,268 @@
+// Barrett reduction: an alternative to Montgomery form for moduli that are
+// reused across many reductions (RSA exponentiation, primality testing).
+// Precomputes `mu = floor(2^(2k) / n)` once (`k` = bit length of `n`) and
+// then reduces any `x < 2^(2k)` with only multiplies/shifts and at most two
+// conditional subtractions of `n` — no hardware division in the hot path.
+//
+// See HAC 14.42 for the reduction formula and its `r < 3n` error bound,
+// which is why two trailing subtractions always suffice to land in [0, n).
+
+use super::mul_asm::{greater_equal_modulus, mul_accumulate, subtract_modulus};
+
+/// Precomputed Barrett reduction constants for a fixed modulus.
+pub struct BarrettReducer {
+    n: Vec<u64>,
+    /// Bit length of `n`, i.e. `k` such that `2^(k-1) <= n < 2^k`.
+    k: usize,
+    /// `floor(2^(2k) / n)`.
+    mu: Vec<u64>,
+}
+
+impl BarrettReducer {
+    /// Precomputes `mu` for `n`. `n` must be nonzero.
+    pub fn new(n: &[u64]) -> Self {
+        let k = bit_length(n);
+        assert!(k > 0, "Barrett reduction requires a nonzero modulus");
+
+        let mut two_2k = vec![0u64; (2 * k) / 64 + 2];
+        set_bit(&mut two_2k, 2 * k);
+        let (mu, _) = divmod(&two_2k, n);
+
+        BarrettReducer { n: n.to_vec(), k, mu }
+    }
+
+    /// Reduces `x` (with `x < 2^(2k)`) modulo `n`, without a hardware divide.
+    pub fn reduce(&self, x: &[u64]) -> Vec<u64> {
+        let k = self.k;
+
+        // q1 = x >> (k-1), q2 = q1 * mu, q3 = q2 >> (k+1)
+        let q1 = shr_bits(x, k.saturating_sub(1));
+        let mut q2 = vec![0u64; q1.len() + self.mu.len()];
+        mul_accumulate(&mut q2, &q1, &self.mu);
+        let q3 = shr_bits(&q2, k + 1);
+
+        // r = x - q3*n, then at most two conditional subtractions of n.
+        let mut q3n = vec![0u64; q3.len() + self.n.len()];
+        mul_accumulate(&mut q3n, &q3, &self.n);
+        let mut r = x.to_vec();
+        sub_generic(&mut r, &q3n);
+
+        if ge_generic(&r, &self.n) {
+            sub_generic(&mut r, &self.n);
+        }
+        if ge_generic(&r, &self.n) {
+            sub_generic(&mut r, &self.n);
+        }
+
+        r.resize(self.n.len(), 0);
+        r
+    }
+
+    /// `base^exp mod n`, via right-to-left binary square-and-multiply using
+    /// `reduce` in place of a schoolbook modulus on every multiply.
+    pub fn mod_exp(&self, base: &[u64], exp: &[u64]) -> Vec<u64> {
+        let s = self.n.len();
+
+        let (_, mut base) = divmod(base, &self.n);
+        base.resize(s, 0);
+
+        let mut one = vec![0u64; s];
+        one[0] = 1;
+        let (_, mut result) = divmod(&one, &self.n);
+        result.resize(s, 0);
+
+        for &limb in exp {
+            for bit in 0..64 {
+                if (limb >> bit) & 1 == 1 {
+                    result = self.mul_mod(&result, &base);
+                }
+                base = self.mul_mod(&base, &base);
+            }
+        }
+
+        result
+    }
+
+    fn mul_mod(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut product = vec![0u64; a.len() + b.len()];
+        mul_accumulate(&mut product, a, b);
+        let mut r = self.reduce(&product);
+        r.resize(self.n.len(), 0);
+        r
+    }
+}
+
+/// Number of bits needed to represent `x` (0 for `x == 0`).
+fn bit_length(x: &[u64]) -> usize {
+    for i in (0..x.len()).rev() {
+        if x[i] != 0 {
+            return i * 64 + (64 - x[i].leading_zeros() as usize);
+        }
+    }
+    0
+}
+
+fn set_bit(x: &mut Vec<u64>, i: usize) {
+    let limb = i / 64;
+    if limb >= x.len() {
+        x.resize(limb + 1, 0);
+    }
+    x[limb] |= 1 << (i % 64);
+}
+
+fn get_bit(x: &[u64], i: usize) -> u64 {
+    let limb = i / 64;
+    if limb >= x.len() {
+        0
+    } else {
+        (x[limb] >> (i % 64)) & 1
+    }
+}
+
+/// `x >> bits`, as a little-endian limb vector the same number of limbs
+/// shorter (in whole words) as `bits` demands.
+fn shr_bits(x: &[u64], bits: usize) -> Vec<u64> {
+    let word_shift = bits / 64;
+    let bit_shift = bits % 64;
+    if word_shift >= x.len() {
+        return vec![0u64];
+    }
+
+    let mut out = vec![0u64; x.len() - word_shift];
+    for i in 0..out.len() {
+        let lo = x[word_shift + i] >> bit_shift;
+        let hi = if bit_shift != 0 && word_shift + i + 1 < x.len() {
+            x[word_shift + i + 1] << (64 - bit_shift)
+        } else {
+            0
+        };
+        out[i] = lo | hi;
+    }
+    out
+}
+
+/// `a >= b`, comparing arbitrary-length little-endian limb vectors (missing
+/// limbs on either side are treated as zero).
+fn ge_generic(a: &[u64], b: &[u64]) -> bool {
+    let len = a.len().max(b.len());
+    for i in (0..len).rev() {
+        let av = a.get(i).copied().unwrap_or(0);
+        let bv = b.get(i).copied().unwrap_or(0);
+        if av != bv {
+            return av > bv;
+        }
+    }
+    true
+}
+
+/// `a -= b` in place, growing `a` if `b` is longer. Callers are expected to
+/// know `a >= b`.
+fn sub_generic(a: &mut Vec<u64>, b: &[u64]) {
+    if b.len() > a.len() {
+        a.resize(b.len(), 0);
+    }
+    let mut borrow = false;
+    for i in 0..a.len() {
+        let bi = b.get(i).copied().unwrap_or(0);
+        let (diff, b1) = a[i].overflowing_sub(bi);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        a[i] = diff;
+        borrow = b1 || b2;
+    }
+}
+
+/// `(floor(x / n), x mod n)` via schoolbook binary long division. Used both
+/// to precompute `mu` and to bring a fresh value into `[0, n)` before the
+/// fast `reduce` path takes over.
+fn divmod(x: &[u64], n: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    let top_bits = bit_length(x);
+    let mut q = vec![0u64; top_bits / 64 + 1];
+    let mut r = vec![0u64; n.len()];
+    if top_bits == 0 {
+        return (q, r);
+    }
+
+    for i in (0..top_bits).rev() {
+        let mut carry = 0u64;
+        for limb in r.iter_mut() {
+            let new_carry = *limb >> 63;
+            *limb = (*limb << 1) | carry;
+            carry = new_carry;
+        }
+        if get_bit(x, i) == 1 {
+            r[0] |= 1;
+        }
+        if greater_equal_modulus(&r, n) {
+            subtract_modulus(&mut r, n);
+            set_bit(&mut q, i);
+        }
+    }
+
+    (q, r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reduce_matches_u128_remainder() {
+        let n = vec![13u64];
+        let reducer = BarrettReducer::new(&n);
+
+        for (a, b) in [(10u64, 11u64), (0, 5), (12, 12), (1, 1)] {
+            let mut product = vec![0u64; 2];
+            mul_accumulate(&mut product, &[a], &[b]);
+            let got = reducer.reduce(&product);
+            let expected = (a as u128 * b as u128 % 13) as u64;
+            assert_eq!(got[0], expected, "a={a} b={b}");
+        }
+    }
+
+    #[test]
+    fn test_reduce_matches_plain_mod_large_modulus() {
+        let n = vec![4294967311u64]; // first prime above u32::MAX
+        let reducer = BarrettReducer::new(&n);
+
+        let a = 123456789u64;
+        let b = 987654321u64;
+        let mut product = vec![0u64; 2];
+        mul_accumulate(&mut product, &[a], &[b]);
+
+        let got = reducer.reduce(&product);
+        let expected = (a as u128 * b as u128 % n[0] as u128) as u64;
+        assert_eq!(got[0], expected);
+    }
+
+    #[test]
+    fn test_mod_exp_matches_plain_pow_mod() {
+        fn pow_mod_u64(base: u64, exp: u64, n: u64) -> u64 {
+            let mut result: u128 = 1;
+            let mut base = base as u128 % n as u128;
+            let mut exp = exp;
+            let n = n as u128;
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    result = result * base % n;
+                }
+                base = base * base % n;
+                exp >>= 1;
+            }
+            result as u64
+        }
+
+        let cases: [(u64, u64, u64); 4] = [
+            (7, 13, 1000000007),
+            (2, 1000, 1000000007),
+            (123456789, 987654321, 4294967311),
+            (0, 5, 97),
+        ];
+
+        for (base, exp, n) in cases {
+            let reducer = BarrettReducer::new(&[n]);
+            let got = reducer.mod_exp(&[base], &[exp]);
+            let expected = pow_mod_u64(base, exp, n);
+            assert_eq!(got[0], expected, "base={base} exp={exp} n={n}");
+        }
+    }
+}
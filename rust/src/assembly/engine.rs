@@ -0,0 +1,170 @@
+// Pluggable modular-exponentiation backend.
+//
+// Mirrors the split between generic RSA code and the modular-exponentiation
+// primitive: callers ask `current_engine()` for "the mod-exp engine" rather
+// than calling `mul_asm::mod_exp` directly, so a future hardware/SIMD
+// backend can be swapped in without touching call sites.
+
+use super::barrett::BarrettReducer;
+use super::mul_asm::{self, asm_available, greater_equal_modulus, mul_accumulate, subtract_modulus};
+
+/// A modular-exponentiation engine over little-endian `u64` limb arrays.
+pub trait ModExp {
+    fn mod_exp(&self, base: &[u64], exp: &[u64], modulus: &[u64]) -> Vec<u64>;
+}
+
+/// Pure-Rust square-and-multiply, reducing with schoolbook binary long
+/// division after every multiply. Slower than `MontgomeryModExp`, but has
+/// no Montgomery-form setup cost and serves as the engine's safe fallback.
+pub struct SoftwareModExp;
+
+impl ModExp for SoftwareModExp {
+    fn mod_exp(&self, base: &[u64], exp: &[u64], modulus: &[u64]) -> Vec<u64> {
+        let mut result = {
+            let mut one = vec![0u64; modulus.len()];
+            one[0] = 1;
+            one
+        };
+        let mut base = mod_reduce(base, modulus);
+
+        for &limb in exp {
+            for bit in 0..64 {
+                if (limb >> bit) & 1 == 1 {
+                    result = mul_mod(&result, &base, modulus);
+                }
+                base = mul_mod(&base, &base, modulus);
+            }
+        }
+
+        result
+    }
+}
+
+/// Montgomery-form square-and-multiply, via `mul_asm::mod_exp`. The engine
+/// to prefer once the assembly-accelerated `mul_u64`/`mul_accumulate` paths
+/// are available, since it's the one built to exploit them.
+pub struct MontgomeryModExp;
+
+impl ModExp for MontgomeryModExp {
+    fn mod_exp(&self, base: &[u64], exp: &[u64], modulus: &[u64]) -> Vec<u64> {
+        mul_asm::mod_exp(base, exp, modulus)
+    }
+}
+
+/// Barrett-reduction square-and-multiply, via `BarrettReducer`. An
+/// alternative to `MontgomeryModExp` for moduli that are reused across many
+/// reductions but where the up-front Montgomery setup isn't wanted — Barrett
+/// precomputes `mu` once per `mod_exp` call and reduces with multiplies and
+/// shifts instead of Montgomery's radix conversions.
+pub struct BarrettModExp;
+
+impl ModExp for BarrettModExp {
+    fn mod_exp(&self, base: &[u64], exp: &[u64], modulus: &[u64]) -> Vec<u64> {
+        BarrettReducer::new(modulus).mod_exp(base, exp)
+    }
+}
+
+/// Returns the mod-exp engine callers should use right now: the Montgomery
+/// engine when assembly acceleration is available (set via `init_asm`),
+/// otherwise the pure-Rust software engine.
+pub fn current_engine() -> Box<dyn ModExp> {
+    if asm_available() {
+        Box::new(MontgomeryModExp)
+    } else {
+        Box::new(SoftwareModExp)
+    }
+}
+
+fn mul_mod(a: &[u64], b: &[u64], n: &[u64]) -> Vec<u64> {
+    let mut product = vec![0u64; a.len() + b.len()];
+    mul_accumulate(&mut product, a, b);
+    mod_reduce(&product, n)
+}
+
+/// Index of the highest set bit in `x` (0-based, limb 0 = least
+/// significant), or `None` if `x` is zero.
+fn highest_bit_index(x: &[u64]) -> Option<usize> {
+    for i in (0..x.len()).rev() {
+        if x[i] != 0 {
+            return Some(i * 64 + (63 - x[i].leading_zeros() as usize));
+        }
+    }
+    None
+}
+
+fn get_bit(x: &[u64], i: usize) -> u64 {
+    let limb = i / 64;
+    let bit = i % 64;
+    if limb >= x.len() {
+        0
+    } else {
+        (x[limb] >> bit) & 1
+    }
+}
+
+/// `x mod n` via schoolbook binary long division: shift the running
+/// remainder left one bit at a time, OR in the next bit of `x`, and
+/// subtract `n` whenever the remainder reaches it.
+fn mod_reduce(x: &[u64], n: &[u64]) -> Vec<u64> {
+    let mut r = vec![0u64; n.len()];
+    let Some(top) = highest_bit_index(x) else {
+        return r;
+    };
+
+    for i in (0..=top).rev() {
+        let mut carry = 0u64;
+        for limb in r.iter_mut() {
+            let new_carry = *limb >> 63;
+            *limb = (*limb << 1) | carry;
+            carry = new_carry;
+        }
+        if get_bit(x, i) == 1 {
+            r[0] |= 1;
+        }
+        if greater_equal_modulus(&r, n) {
+            subtract_modulus(&mut r, n);
+        }
+    }
+
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_software_mod_exp_matches_montgomery() {
+        let base = vec![123456789u64];
+        let exp = vec![987654321u64];
+        let n = vec![4294967311u64]; // first prime above u32::MAX
+
+        let software = SoftwareModExp.mod_exp(&base, &exp, &n);
+        let montgomery = MontgomeryModExp.mod_exp(&base, &exp, &n);
+        assert_eq!(software, montgomery);
+    }
+
+    #[test]
+    fn test_barrett_mod_exp_matches_software() {
+        let base = vec![123456789u64];
+        let exp = vec![987654321u64];
+        let n = vec![4294967311u64]; // first prime above u32::MAX
+
+        let barrett = BarrettModExp.mod_exp(&base, &exp, &n);
+        let software = SoftwareModExp.mod_exp(&base, &exp, &n);
+        assert_eq!(barrett, software);
+    }
+
+    #[test]
+    fn test_current_engine_matches_asm_availability() {
+        // asm_available() defaults to false until init_asm() detects a
+        // backend, so the default engine should agree with SoftwareModExp.
+        let base = vec![7u64];
+        let exp = vec![13u64];
+        let n = vec![1000000007u64];
+
+        let via_current = current_engine().mod_exp(&base, &exp, &n);
+        let via_software = SoftwareModExp.mod_exp(&base, &exp, &n);
+        assert_eq!(via_current, via_software);
+    }
+}
@@ -2,6 +2,13 @@
 // Provides Assembly-optimized multiplication for RSA operations
 
 pub mod mul_asm;
+pub mod barrett;
+pub mod montgomery64;
+pub mod engine;
 
 pub use mul_asm::asm_available;
-pub use mul_asm::init_asm;
\ No newline at end of file
+pub use mul_asm::init_asm;
+pub use mul_asm::{mod_exp, mont_mul, setup_montgomery};
+pub use barrett::BarrettReducer;
+pub use montgomery64::Montgomery64;
+pub use engine::{current_engine, BarrettModExp, ModExp, MontgomeryModExp, SoftwareModExp};
\ No newline at end of file
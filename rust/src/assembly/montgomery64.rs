@@ -0,0 +1,134 @@
+// Single-word Montgomery arithmetic for odd 64-bit moduli. The multi-limb
+// `setup_montgomery`/`mont_mul` pair in `mul_asm` targets RSA-sized moduli;
+// this is the same idea specialized to a single `u64`, for the hot inner
+// loop of Miller-Rabin primality testing (and other small modular powers)
+// where a full limb-vector Montgomery setup would be overkill.
+
+/// Precomputed single-word Montgomery constants for a fixed odd modulus.
+pub struct Montgomery64 {
+    n: u64,
+    /// `n_dash` such that `n * n_dash ≡ -1 (mod 2^64)`.
+    n_dash: u64,
+    /// `(2^64 mod n)^2 mod n`.
+    r2: u64,
+}
+
+impl Montgomery64 {
+    /// Precomputes `n_dash` and `r2` for `n`. `n` must be odd.
+    pub fn new(n: u64) -> Self {
+        assert!(n % 2 == 1, "Montgomery64 requires an odd modulus");
+
+        // n_dash such that n * n_dash ≡ -1 (mod 2^64), via the same
+        // Dussé-Kaliski bit-lifting construction used for the multi-limb
+        // `n'` in `mul_asm::setup_montgomery`.
+        let mut y = 1u64;
+        for i in 1..64 {
+            let t = n.wrapping_mul(y);
+            if (t >> i) & 1 == 1 {
+                y |= 1 << i;
+            }
+        }
+        let n_dash = y.wrapping_neg();
+
+        let r_mod_n = ((1u128 << 64) % n as u128) as u64;
+        let r2 = (r_mod_n as u128 * r_mod_n as u128 % n as u128) as u64;
+
+        Montgomery64 { n, n_dash, r2 }
+    }
+
+    /// REDC: `t * R^-1 mod n`, for `t < n * R`, without a hardware divide.
+    ///
+    /// Summing `t + m*n` directly in `u128` would overflow for `n` above
+    /// `2^63` (both can individually approach `2^128`), so the high and low
+    /// 64-bit halves are added with explicit carry tracking instead, and any
+    /// carry out of the top word is folded back in via `u128` only once the
+    /// value is already small (at most one bit beyond 64).
+    pub fn reduce(&self, t: u128) -> u64 {
+        let t_lo = t as u64;
+        let t_hi = (t >> 64) as u64;
+
+        let m = t_lo.wrapping_mul(self.n_dash);
+        let mn = m as u128 * self.n as u128;
+        let mn_lo = mn as u64;
+        let mn_hi = (mn >> 64) as u64;
+
+        // m is chosen so t_lo + mn_lo always cancels to 0 mod 2^64; only the
+        // carry out of that addition (if any) matters for the high half.
+        let (_, carry_lo) = t_lo.overflowing_add(mn_lo);
+        let (high_sum, carry1) = t_hi.overflowing_add(mn_hi);
+        let (high_sum, carry2) = high_sum.overflowing_add(carry_lo as u64);
+        let overflow = carry1 || carry2;
+
+        let mut result = high_sum as u128 + if overflow { 1u128 << 64 } else { 0 };
+        if result >= self.n as u128 {
+            result -= self.n as u128;
+        }
+        if result >= self.n as u128 {
+            result -= self.n as u128;
+        }
+        result as u64
+    }
+
+    /// Maps a plain value into Montgomery form: `x * R mod n`.
+    pub fn form(&self, x: u64) -> u64 {
+        self.reduce(x as u128 * self.r2 as u128)
+    }
+
+    /// `x * y mod n` for plain (non-Montgomery-form) `x` and `y`: one REDC
+    /// strips the extra factor of `R` introduced by the plain product, a
+    /// second REDC against `r2` puts it back, leaving `x*y mod n` exactly —
+    /// two multiply-and-shifts standing in for a single hardware divide.
+    pub fn mul(&self, x: u64, y: u64) -> u64 {
+        let partial = self.reduce(x as u128 * y as u128);
+        self.reduce(partial as u128 * self.r2 as u128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_and_reduce_round_trip() {
+        let mont = Montgomery64::new(1000000007);
+        for x in [0u64, 1, 42, 999999999] {
+            let formed = mont.form(x);
+            let back = mont.reduce(formed as u128);
+            assert_eq!(back, x % 1000000007, "x={x}");
+        }
+    }
+
+    #[test]
+    fn test_mul_matches_u128_reference() {
+        let n = 4294967311u64; // first prime above u32::MAX
+        let mont = Montgomery64::new(n);
+
+        for (x, y) in [(123456789u64, 987654321u64), (0, 5), (n - 1, n - 1), (1, 1)] {
+            let got = mont.mul(x, y);
+            let expected = (x as u128 * y as u128 % n as u128) as u64;
+            assert_eq!(got, expected, "x={x} y={y}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "odd modulus")]
+    fn test_new_rejects_even_modulus() {
+        Montgomery64::new(100);
+    }
+
+    #[test]
+    fn test_mul_matches_u128_reference_for_modulus_above_2_63() {
+        let n = u64::MAX - 58; // odd, just below 2^64
+        let mont = Montgomery64::new(n);
+
+        for (x, y) in [
+            (n - 1, n - 1),
+            (1u64 << 63, (1u64 << 63) + 1),
+            (123456789012345u64, 987654321098765u64),
+        ] {
+            let got = mont.mul(x % n, y % n);
+            let expected = (x as u128 * y as u128 % n as u128) as u64;
+            assert_eq!(got, expected, "x={x} y={y} n={n}");
+        }
+    }
+}
@@ -12,19 +12,93 @@ pub fn asm_available() -> bool {
 }
 
 /// Initialize assembly support
+///
+/// Probes the target architecture for a native 64x64->128 multiply
+/// instruction (`mulq` on x86_64, `mul`/`umulh` on aarch64). On any other
+/// target `mul_u64` falls back to the portable 32-bit-split algorithm.
 pub fn init_asm() {
-    // For now, assembly is not available
-    // This can be extended to load compiled assembly code
-    ASM_AVAILABLE.store(false, Ordering::Relaxed);
+    let available = cfg!(any(target_arch = "x86_64", target_arch = "aarch64"));
+    ASM_AVAILABLE.store(available, Ordering::Relaxed);
 }
 
 /// Multiply two u64 numbers using assembly-optimized routine
 /// Returns (high, low) parts of the 128-bit result
 pub fn mul_u64(a: u64, b: u64) -> (u64, u64) {
-    // Fallback to Rust implementation
-    // In a full implementation, this would call the assembly routine
-    let result = a as u128 * b as u128;
-    ((result >> 64) as u64, result as u64)
+    if asm_available() {
+        #[cfg(target_arch = "x86_64")]
+        {
+            return mul_u64_x86_64(a, b);
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            return mul_u64_aarch64(a, b);
+        }
+    }
+    mul_u64_split_fallback(a, b)
+}
+
+/// 64x64->128 multiply via the x86_64 `mul` instruction, which leaves the
+/// result as RDX:RAX.
+#[cfg(target_arch = "x86_64")]
+fn mul_u64_x86_64(a: u64, b: u64) -> (u64, u64) {
+    let lo: u64;
+    let hi: u64;
+    unsafe {
+        core::arch::asm!(
+            "mul {b}",
+            b = in(reg) b,
+            inout("rax") a => lo,
+            out("rdx") hi,
+            options(pure, nomem, nostack),
+        );
+    }
+    (hi, lo)
+}
+
+/// 64x64->128 multiply via the aarch64 `mul`/`umulh` pair, which compute the
+/// low and high halves of the product separately.
+#[cfg(target_arch = "aarch64")]
+fn mul_u64_aarch64(a: u64, b: u64) -> (u64, u64) {
+    let lo: u64;
+    let hi: u64;
+    unsafe {
+        core::arch::asm!(
+            "mul {lo}, {a}, {b}",
+            "umulh {hi}, {a}, {b}",
+            a = in(reg) a,
+            b = in(reg) b,
+            lo = out(reg) lo,
+            hi = out(reg) hi,
+            options(pure, nomem, nostack),
+        );
+    }
+    (hi, lo)
+}
+
+/// Portable 64x64->128 multiply for targets without a native wide-multiply
+/// instruction (or where one hasn't been wired up yet): split each operand
+/// into 32-bit halves and combine the four 32x32->64 partial products by
+/// hand, the classic algorithm behind emulated wide multiplication.
+fn mul_u64_split_fallback(a: u64, b: u64) -> (u64, u64) {
+    let a_lo = a & 0xFFFF_FFFF;
+    let a_hi = a >> 32;
+    let b_lo = b & 0xFFFF_FFFF;
+    let b_hi = b >> 32;
+
+    let lo_lo = a_lo.wrapping_mul(b_lo);
+    let lo_hi = a_lo.wrapping_mul(b_hi);
+    let hi_lo = a_hi.wrapping_mul(b_lo);
+    let hi_hi = a_hi.wrapping_mul(b_hi);
+
+    let cross = lo_hi
+        .wrapping_add(lo_lo >> 32)
+        .wrapping_add(hi_lo & 0xFFFF_FFFF);
+    let carry = cross >> 32;
+
+    let low = (lo_lo & 0xFFFF_FFFF) | (cross << 32);
+    let high = hi_hi.wrapping_add(hi_lo >> 32).wrapping_add(carry);
+
+    (high, low)
 }
 
 /// Multiply and accumulate: result += a * b
@@ -36,34 +110,322 @@ pub fn mul_accumulate(result: &mut [u64], a: &[u64], b: &[u64]) {
         let mut carry = 0u128;
         for (j, bj) in b.iter().enumerate() {
             let idx = i + j;
-            if idx < result.len() {
-                let prod = *ai as u128 * *bj as u128 + result[idx] as u128 + carry;
-                result[idx] = prod as u64;
-                carry = prod >> 64;
+            if idx >= result.len() {
+                break;
             }
+            let prod = *ai as u128 * *bj as u128 + result[idx] as u128 + carry;
+            result[idx] = prod as u64;
+            carry = prod >> 64;
         }
-        if let Some(dest) = result.get_mut(a.len() + b.len() - 1) {
-            *dest = carry as u64;
+        // The outer limb's leftover carry lands at i + b.len() and must be
+        // added (not stored), since that slot may already hold a partial
+        // sum from an earlier outer limb — and that addition can itself
+        // carry further, so keep propagating until it doesn't.
+        let mut idx = i + b.len();
+        while carry > 0 && idx < result.len() {
+            let sum = result[idx] as u128 + carry;
+            result[idx] = sum as u64;
+            carry = sum >> 64;
+            idx += 1;
         }
     }
 }
 
-/// Montgomery multiplication setup
-/// Returns Montgomery constants for efficient modular multiplication
-pub fn setup_montgomery(n: &[u64]) -> (Vec<u64>, u64) {
-    // Compute n' such that n * n' ≡ -1 (mod 2^64)
-    let mut np = 0u64;
-    for i in 0..64 {
-        np <<= 1;
-        if (np & 1) == 0 && ((n[0] * np) & 1) == 1 {
-            np |= 1;
+/// Compare two little-endian limb arrays (limb 0 = least significant),
+/// walking from the most-significant limb down. `num` may be shorter than
+/// `n`; missing high limbs are treated as zero.
+pub(super) fn greater_equal_modulus(num: &[u64], n: &[u64]) -> bool {
+    for i in (0..n.len()).rev() {
+        let a = num.get(i).copied().unwrap_or(0);
+        let b = n[i];
+        if a != b {
+            return a > b;
         }
     }
-    np = !np + 1;
+    true
+}
+
+/// `num -= n`, as a little-endian borrow-propagating subtraction. Callers
+/// are expected to know `num >= n` (or to be relying on a dropped overflow
+/// limb cancelling out any underflow borrow, as in `double_mod`).
+pub(super) fn subtract_modulus(num: &mut [u64], n: &[u64]) {
+    let mut borrow = false;
+    for i in 0..n.len() {
+        let (diff, b1) = num[i].overflowing_sub(n[i]);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        num[i] = diff;
+        borrow = b1 || b2;
+    }
+}
+
+/// `x = (2*x) mod n`, assuming `x < n` on entry. Used to build up powers of
+/// two mod n one bit at a time without a full bignum multiply/divide.
+fn double_mod(x: &mut [u64], n: &[u64]) {
+    let mut carry = 0u64;
+    for limb in x.iter_mut() {
+        let new_carry = *limb >> 63;
+        *limb = (*limb << 1) | carry;
+        carry = new_carry;
+    }
+    if carry == 1 {
+        // The true (untruncated) value is 2^(64*len) + x; since n fits in
+        // `len` limbs, that's always >= n, so subtracting n from the
+        // truncated limbs is correct even if it also borrows out of the
+        // top limb — that borrow exactly cancels the dropped carry bit.
+        subtract_modulus(x, n);
+    } else if greater_equal_modulus(x, n) {
+        subtract_modulus(x, n);
+    }
+}
 
-    // Compute R = 2^(64*len) mod n
+/// `2^(64 * n.len()) mod n`, computed via repeated `double_mod` instead of
+/// a full-width divide.
+fn r_mod_n(n: &[u64]) -> Vec<u64> {
     let mut r = vec![0u64; n.len()];
-    r[n.len() - 1] = 1u64 << 63;
+    r[0] = 1;
+    for _ in 0..(64 * n.len()) {
+        double_mod(&mut r, n);
+    }
+    r
+}
+
+/// Montgomery multiplication setup.
+/// Returns `(R mod n, n', R^2 mod n)`, the constants needed to enter/exit
+/// Montgomery form (`R`, `R^2`) and to run `mont_mul` (`n'`).
+pub fn setup_montgomery(n: &[u64]) -> (Vec<u64>, u64, Vec<u64>) {
+    // Compute n' such that n * n' ≡ -1 (mod 2^64), via the standard
+    // Dussé-Kaliski bit-lifting construction: y starts as n's inverse mod 2
+    // (trivially 1, since n is odd) and each iteration extends it to be
+    // correct one more bit, checking whether n*y already agrees with 1 at
+    // bit i and flipping that bit of y if not.
+    let mut y = 1u64;
+    for i in 1..64 {
+        let t = n[0].wrapping_mul(y);
+        if (t >> i) & 1 == 1 {
+            y |= 1 << i;
+        }
+    }
+    let np = y.wrapping_neg();
+
+    let r = r_mod_n(n);
+
+    // R^2 mod n = (R mod n) * 2^(64*len) mod n, i.e. R doubled 64*len more
+    // times — avoids a full bignum multiply to square it.
+    let mut r2 = r.clone();
+    for _ in 0..(64 * n.len()) {
+        double_mod(&mut r2, n);
+    }
+
+    (r, np, r2)
+}
+
+/// CIOS (Coarsely Integrated Operand Scanning) Montgomery multiplication:
+/// `mont_mul(a, b, n, np) = a * b * R^-1 mod n`, where `R = 2^(64*s)` and
+/// `s = n.len()`. `a` and `b` must already be in Montgomery form (or be
+/// one Montgomery-form value and one plain value, per the usual trick for
+/// converting in/out of Montgomery form).
+pub fn mont_mul(a: &[u64], b: &[u64], n: &[u64], np: u64) -> Vec<u64> {
+    let s = n.len();
+    let mut t = vec![0u64; s + 2];
+
+    for i in 0..s {
+        let bi = b.get(i).copied().unwrap_or(0);
+
+        // t += a * b[i]
+        let mut carry: u128 = 0;
+        for j in 0..s {
+            let aj = a.get(j).copied().unwrap_or(0);
+            let prod = aj as u128 * bi as u128 + t[j] as u128 + carry;
+            t[j] = prod as u64;
+            carry = prod >> 64;
+        }
+        let sum = t[s] as u128 + carry;
+        t[s] = sum as u64;
+        t[s + 1] = t[s + 1].wrapping_add((sum >> 64) as u64);
+
+        // m = (t[0] * np) mod 2^64
+        let m = t[0].wrapping_mul(np);
+
+        // t += m * n
+        let mut carry2: u128 = 0;
+        for j in 0..s {
+            let prod = m as u128 * n[j] as u128 + t[j] as u128 + carry2;
+            t[j] = prod as u64;
+            carry2 = prod >> 64;
+        }
+        let sum2 = t[s] as u128 + carry2;
+        t[s] = sum2 as u64;
+        t[s + 1] = t[s + 1].wrapping_add((sum2 >> 64) as u64);
+
+        // Shift t right by one limb (t[0] is guaranteed zero by construction).
+        for k in 0..s + 1 {
+            t[k] = t[k + 1];
+        }
+        t[s + 1] = 0;
+    }
 
-    (r, np)
-}
\ No newline at end of file
+    let mut result = t[0..s].to_vec();
+    if t[s] != 0 || greater_equal_modulus(&result, n) {
+        subtract_modulus(&mut result, n);
+    }
+
+    result
+}
+
+/// Modular exponentiation `base^exp mod n` over little-endian `u64` limb
+/// arrays, via right-to-left binary square-and-multiply in Montgomery form.
+pub fn mod_exp(base: &[u64], exp: &[u64], n: &[u64]) -> Vec<u64> {
+    let s = n.len();
+    let (_r, np, r2) = setup_montgomery(n);
+
+    let mut base_padded = base.to_vec();
+    base_padded.resize(s, 0);
+    let mut base_mont = mont_mul(&base_padded, &r2, n, np);
+
+    let mut one = vec![0u64; s];
+    one[0] = 1;
+    let mut result_mont = mont_mul(&one, &r2, n, np); // R mod n, i.e. 1 in Montgomery form
+
+    for &limb in exp {
+        for bit in 0..64 {
+            if (limb >> bit) & 1 == 1 {
+                result_mont = mont_mul(&result_mont, &base_mont, n, np);
+            }
+            base_mont = mont_mul(&base_mont, &base_mont, n, np);
+        }
+    }
+
+    mont_mul(&result_mont, &one, n, np)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_accumulate_multi_limb() {
+        // (2^64 + 1)^2 = 2^128 + 2*2^64 + 1, i.e. limbs [1, 2, 1].
+        let a = vec![1u64, 1u64];
+        let b = vec![1u64, 1u64];
+        let mut result = vec![0u64; 4];
+        mul_accumulate(&mut result, &a, &b);
+        assert_eq!(result, vec![1, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_mul_accumulate_accumulates_onto_existing_value() {
+        // result already holds 5; add 3 * 4 = 12 onto it.
+        let mut result = vec![5u64, 0u64];
+        mul_accumulate(&mut result, &[3u64], &[4u64]);
+        assert_eq!(result, vec![17, 0]);
+    }
+
+    /// Plain schoolbook `base^exp mod n`, used as a reference oracle for
+    /// `mod_exp`. Only exercised with single-limb moduli/exponents in
+    /// tests, where `u128` arithmetic is exact.
+    fn pow_mod_u64(base: u64, exp: u64, n: u64) -> u64 {
+        let mut result: u128 = 1;
+        let mut base = base as u128 % n as u128;
+        let mut exp = exp;
+        let n = n as u128;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base % n;
+            }
+            base = base * base % n;
+            exp >>= 1;
+        }
+        result as u64
+    }
+
+    #[test]
+    fn test_setup_montgomery_round_trip() {
+        let n = vec![1000000007u64];
+        let (r, np, r2) = setup_montgomery(&n);
+
+        // n * np ≡ -1 (mod 2^64)
+        assert_eq!(n[0].wrapping_mul(np), u64::MAX);
+
+        // r == 2^64 mod n, r2 == r^2 mod n, both checked against u128 math.
+        let expected_r = ((1u128 << 64) % n[0] as u128) as u64;
+        assert_eq!(r[0], expected_r);
+        let expected_r2 = (expected_r as u128 * expected_r as u128 % n[0] as u128) as u64;
+        assert_eq!(r2[0], expected_r2);
+    }
+
+    #[test]
+    fn test_mod_exp_matches_plain_pow_mod_single_limb() {
+        let cases: [(u64, u64, u64); 5] = [
+            (7, 13, 1000000007),
+            (2, 1000, 1000000007),
+            (123456789, 987654321, 4294967311), // first prime above u32::MAX
+            (0, 5, 97),
+            (5, 0, 97),
+        ];
+
+        for (base, exp, n) in cases {
+            let got = mod_exp(&[base], &[exp], &[n]);
+            let expected = pow_mod_u64(base, exp, n);
+            assert_eq!(got[0], expected, "base={base} exp={exp} n={n}");
+        }
+    }
+
+    #[test]
+    fn test_mont_mul_matches_plain_product_mod_n() {
+        let n = vec![1000000007u64];
+        let (_r, np, r2) = setup_montgomery(&n);
+
+        let a = vec![123456u64];
+        let b = vec![654321u64];
+
+        let a_mont = mont_mul(&a, &r2, &n, np);
+        let b_mont = mont_mul(&b, &r2, &n, np);
+        let product_mont = mont_mul(&a_mont, &b_mont, &n, np);
+
+        let one = vec![1u64];
+        let product = mont_mul(&product_mont, &one, &n, np);
+
+        let expected = (a[0] as u128 * b[0] as u128 % n[0] as u128) as u64;
+        assert_eq!(product[0], expected);
+    }
+
+    #[test]
+    fn test_mul_u64_matches_u128_reference() {
+        let cases: [(u64, u64); 6] = [
+            (0, 0),
+            (1, u64::MAX),
+            (u64::MAX, u64::MAX),
+            (123456789, 987654321),
+            (1u64 << 32, 1u64 << 32),
+            (0xFFFF_FFFF_0000_0001, 0x0000_0001_FFFF_FFFF),
+        ];
+
+        for (a, b) in cases {
+            let (hi, lo) = mul_u64(a, b);
+            let expected = a as u128 * b as u128;
+            let got = ((hi as u128) << 64) | lo as u128;
+            assert_eq!(got, expected, "a={a} b={b}");
+        }
+    }
+
+    #[test]
+    fn test_mul_u64_split_fallback_matches_u128_reference() {
+        // Exercises the portable path directly, regardless of which path
+        // `mul_u64` itself dispatches to on the host architecture.
+        let cases: [(u64, u64); 6] = [
+            (0, 0),
+            (1, u64::MAX),
+            (u64::MAX, u64::MAX),
+            (123456789, 987654321),
+            (1u64 << 32, 1u64 << 32),
+            (0xFFFF_FFFF_0000_0001, 0x0000_0001_FFFF_FFFF),
+        ];
+
+        for (a, b) in cases {
+            let (hi, lo) = mul_u64_split_fallback(a, b);
+            let expected = a as u128 * b as u128;
+            let got = ((hi as u128) << 64) | lo as u128;
+            assert_eq!(got, expected, "a={a} b={b}");
+        }
+    }
+}
@@ -0,0 +1,307 @@
+// Read-only FUSE view over the `ciphertexts` work-subdir (chunk1-4): each
+// `.rsa` file appears under its original `metadata.filename` and is
+// decrypted lazily, chunk by chunk, as a reader actually touches it, instead
+// of a bulk decrypt-to-disk pass into `decrypted/`. Linux-only because FUSE
+// itself is.
+#![cfg(target_os = "linux")]
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use fuser::{FileAttr, FileType, Filesystem, ReplyAttr, ReplyData, ReplyDirectory, ReplyEntry, Request};
+use openssl::symm::{Cipher, Crypter, Mode};
+
+use crate::{
+    chunk_nonce, validate_rsaf_file, RSAEngine, RsaFileMetadata, AES_NONCE_SIZE, AES_TAG_SIZE,
+    COMPRESSION_ZSTD, FILE_HEADER_SIZE, FILE_MAC_SIZE, WRAPPED_KEY_SIZE,
+};
+
+const TTL: Duration = Duration::from_secs(1);
+const ROOT_INO: u64 = 1;
+
+/// One `.rsa` file exposed under the mount, keyed by inode (2, 3, ...).
+struct MountEntry {
+    name: String,
+    rsa_path: PathBuf,
+    metadata: RsaFileMetadata,
+}
+
+/// `fuser::Filesystem` backing the mount. Owns its own clone of the
+/// keyring rather than sharing the UI's `Rc<RefCell<RSAEngine>>`, since
+/// `fuser::spawn_mount2` runs the filesystem on its own thread.
+pub struct DecryptedFs {
+    rsa_engine: RSAEngine,
+    entries: Vec<MountEntry>,
+    // Content keys already RSA-unwrapped, cached per inode so repeated
+    // reads of the same open file don't re-run a private-key operation.
+    content_keys: HashMap<u64, Vec<u8>>,
+}
+
+impl DecryptedFs {
+    /// Scans `ciphertexts_dir` once at mount time: only each file's header,
+    /// filename and envelope preamble (wrapped key, base nonce, chunk size)
+    /// are read here (via `validate_rsaf_file`), never the encrypted chunks.
+    pub fn new(rsa_engine: RSAEngine, ciphertexts_dir: &Path) -> std::io::Result<Self> {
+        let mut entries = Vec::new();
+        for dir_entry in fs::read_dir(ciphertexts_dir)? {
+            let path = dir_entry?.path();
+            if path.extension().and_then(OsStr::to_str) != Some("rsa") {
+                continue;
+            }
+            if let Ok(metadata) = validate_rsaf_file(&path) {
+                if metadata.version >= 2 {
+                    entries.push(MountEntry { name: metadata.filename.clone(), rsa_path: path, metadata });
+                }
+                // v1 files are skipped: they predate per-chunk AEAD, so
+                // there's nothing here to lazily seek into chunk-by-chunk.
+            }
+        }
+        Ok(Self { rsa_engine, entries, content_keys: HashMap::new() })
+    }
+
+    fn entry_for_ino(&self, ino: u64) -> Option<&MountEntry> {
+        ino.checked_sub(2).and_then(|i| self.entries.get(i as usize))
+    }
+
+    fn attr_for_entry(ino: u64, entry: &MountEntry) -> FileAttr {
+        FileAttr {
+            ino,
+            size: entry.metadata.file_size,
+            blocks: entry.metadata.file_size.div_ceil(512),
+            atime: UNIX_EPOCH,
+            mtime: UNIX_EPOCH,
+            ctime: UNIX_EPOCH,
+            crtime: UNIX_EPOCH,
+            kind: FileType::RegularFile,
+            perm: 0o444,
+            nlink: 1,
+            uid: unsafe { libc::getuid() },
+            gid: unsafe { libc::getgid() },
+            rdev: 0,
+            blksize: 512,
+            flags: 0,
+        }
+    }
+
+    /// RSA-unwraps the content key for `entry`, auto-selecting the matching
+    /// keyring entry by recipient fingerprint the same way the
+    /// `on_decrypt_file` UI handler does, and caches the result under `ino`.
+    fn content_key(&mut self, ino: u64) -> Option<Vec<u8>> {
+        if let Some(key) = self.content_keys.get(&ino) {
+            return Some(key.clone());
+        }
+        let entry = self.entry_for_ino(ino)?;
+        if let Some(fp) = entry.metadata.recipient_fingerprint {
+            if self.rsa_engine.active_fingerprint() != Some(fp) {
+                self.rsa_engine.select_by_fingerprint(&fp)?;
+            }
+        }
+        let mut src_file = fs::File::open(&entry.rsa_path).ok()?;
+        let filename_len = entry.metadata.filename.as_bytes().len();
+        src_file.seek(std::io::SeekFrom::Start((FILE_HEADER_SIZE + filename_len) as u64)).ok()?;
+        let mut wrapped_key = vec![0u8; WRAPPED_KEY_SIZE];
+        src_file.read_exact(&mut wrapped_key).ok()?;
+        let content_key = self.rsa_engine.decrypt(&wrapped_key).ok()?;
+        self.content_keys.insert(ino, content_key.clone());
+        Some(content_key)
+    }
+
+    /// Decrypts exactly the chunks spanning `[offset, offset + size)` and
+    /// returns the requested slice, without reading or decrypting the rest
+    /// of the file. Compressed bodies can't be seeked into chunk-by-chunk
+    /// (zstd frames aren't randomly addressable), so they fall back to a
+    /// one-time full decrypt-and-decompress, cached nowhere further since
+    /// the kernel's page cache already avoids repeat reads of a hot file.
+    fn read_range(&mut self, ino: u64, offset: u64, size: u32) -> Option<Vec<u8>> {
+        let content_key = self.content_key(ino)?;
+        let entry = self.entry_for_ino(ino)?;
+        let base_nonce = entry.metadata.base_nonce.clone()?;
+        let chunk_size = entry.metadata.chunk_size? as u64;
+        let filename_len = entry.metadata.filename.as_bytes().len() as u64;
+        let body_start = FILE_HEADER_SIZE as u64 + filename_len + WRAPPED_KEY_SIZE as u64 + AES_NONCE_SIZE as u64 + 4;
+
+        if entry.metadata.compression_algo == COMPRESSION_ZSTD {
+            let mut src_file = fs::File::open(&entry.rsa_path).ok()?;
+            src_file.seek(std::io::SeekFrom::Start(body_start)).ok()?;
+            // Same derivation `decrypt_file` uses: the on-disk length minus
+            // the header/key/nonce we've already seeked past, minus the
+            // trailing whole-file MAC, leaves exactly the chunk bytes.
+            let total_len = fs::metadata(&entry.rsa_path).ok()?.len();
+            let body_len = total_len.saturating_sub(body_start).saturating_sub(FILE_MAC_SIZE as u64);
+            let plaintext = decrypt_whole_body(&mut src_file, &content_key, &base_nonce, chunk_size as usize, body_len, &entry.metadata)?;
+            let end = (offset + size as u64).min(plaintext.len() as u64) as usize;
+            let start = (offset as usize).min(plaintext.len());
+            return Some(plaintext[start..end].to_vec());
+        }
+
+        let mut out = Vec::with_capacity(size as usize);
+        let mut chunk_index = offset / chunk_size;
+        let mut src_file = fs::File::open(&entry.rsa_path).ok()?;
+        let mut remaining_offset = offset % chunk_size;
+        let mut remaining_len = size as u64;
+
+        while remaining_len > 0 {
+            let chunk_offset = body_start + chunk_index * (chunk_size + AES_TAG_SIZE as u64);
+            src_file.seek(std::io::SeekFrom::Start(chunk_offset)).ok()?;
+            let this_chunk_plain_len = chunk_size.min(entry.metadata.file_size.saturating_sub(chunk_index * chunk_size));
+            if this_chunk_plain_len == 0 {
+                break;
+            }
+            let mut ciphertext = vec![0u8; this_chunk_plain_len as usize];
+            src_file.read_exact(&mut ciphertext).ok()?;
+            let mut tag = [0u8; AES_TAG_SIZE];
+            src_file.read_exact(&mut tag).ok()?;
+
+            let nonce = chunk_nonce(&base_nonce, chunk_index as u32);
+            let mut decrypter = Crypter::new(Cipher::aes_256_gcm(), Mode::Decrypt, &content_key, Some(&nonce)).ok()?;
+            decrypter.set_tag(&tag).ok()?;
+            let mut plain = vec![0u8; ciphertext.len() + AES_TAG_SIZE];
+            let mut written = decrypter.update(&ciphertext, &mut plain).ok()?;
+            written += decrypter.finalize(&mut plain[written..]).ok()?;
+            plain.truncate(written);
+
+            let start = remaining_offset as usize;
+            let end = plain.len().min(start + remaining_len as usize);
+            out.extend_from_slice(&plain[start.min(plain.len())..end]);
+            remaining_len -= (end - start.min(plain.len())) as u64;
+            remaining_offset = 0;
+            chunk_index += 1;
+        }
+
+        Some(out)
+    }
+}
+
+/// Decrypts every chunk of a file's body (bounded by `body_len`, the same
+/// tag-inclusive byte count `decrypt_file` computes) and, if the envelope
+/// was compressed, decompresses the result — returning the original
+/// plaintext in one shot.
+fn decrypt_whole_body(src_file: &mut fs::File, content_key: &[u8], base_nonce: &[u8], chunk_size: usize, body_len: u64, metadata: &RsaFileMetadata) -> Option<Vec<u8>> {
+    let mut decrypted = Vec::new();
+    let mut chunk_index: u32 = 0;
+    let mut buf = vec![0u8; chunk_size];
+    let mut remaining = body_len;
+    while remaining > 0 {
+        // Bounding each read by `remaining` (rather than trusting EOF to
+        // mark the last chunk) keeps this from over-reading into the
+        // trailing whole-file MAC on the final chunk. `remaining` includes
+        // the chunk's trailing tag, so a truncated body can leave fewer
+        // than AES_TAG_SIZE bytes here; treat that as corruption (None)
+        // rather than underflowing the subtraction.
+        let this_chunk_len = remaining.checked_sub(AES_TAG_SIZE as u64)?.min(chunk_size as u64) as usize;
+        src_file.read_exact(&mut buf[..this_chunk_len]).ok()?;
+        let mut tag = [0u8; AES_TAG_SIZE];
+        src_file.read_exact(&mut tag).ok()?;
+
+        let nonce = chunk_nonce(base_nonce, chunk_index);
+        let mut decrypter = Crypter::new(Cipher::aes_256_gcm(), Mode::Decrypt, content_key, Some(&nonce)).ok()?;
+        decrypter.set_tag(&tag).ok()?;
+        let mut plain = vec![0u8; this_chunk_len + AES_TAG_SIZE];
+        let mut written = decrypter.update(&buf[..this_chunk_len], &mut plain).ok()?;
+        written += decrypter.finalize(&mut plain[written..]).ok()?;
+        decrypted.extend_from_slice(&plain[..written]);
+        remaining -= (this_chunk_len + AES_TAG_SIZE) as u64;
+        chunk_index += 1;
+    }
+    if metadata.compression_algo == COMPRESSION_ZSTD {
+        let mut out = Vec::with_capacity(metadata.file_size as usize);
+        zstd::stream::copy_decode(&decrypted[..], &mut out).ok()?;
+        Some(out)
+    } else {
+        Some(decrypted)
+    }
+}
+
+impl Filesystem for DecryptedFs {
+    fn lookup(&mut self, _req: &Request, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        if parent != ROOT_INO {
+            reply.error(libc::ENOENT);
+            return;
+        }
+        let Some(name) = name.to_str() else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+        match self.entries.iter().position(|e| e.name == name) {
+            Some(i) => reply.entry(&TTL, &Self::attr_for_entry(i as u64 + 2, &self.entries[i]), 0),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn getattr(&mut self, _req: &Request, ino: u64, _fh: Option<u64>, reply: ReplyAttr) {
+        if ino == ROOT_INO {
+            let attr = FileAttr {
+                ino: ROOT_INO,
+                size: 0,
+                blocks: 0,
+                atime: UNIX_EPOCH,
+                mtime: UNIX_EPOCH,
+                ctime: UNIX_EPOCH,
+                crtime: UNIX_EPOCH,
+                kind: FileType::Directory,
+                perm: 0o555,
+                nlink: 2,
+                uid: unsafe { libc::getuid() },
+                gid: unsafe { libc::getgid() },
+                rdev: 0,
+                blksize: 512,
+                flags: 0,
+            };
+            reply.attr(&TTL, &attr);
+            return;
+        }
+        match self.entry_for_ino(ino) {
+            Some(entry) => reply.attr(&TTL, &Self::attr_for_entry(ino, entry)),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn readdir(&mut self, _req: &Request, ino: u64, _fh: u64, offset: i64, mut reply: ReplyDirectory) {
+        if ino != ROOT_INO {
+            reply.error(libc::ENOENT);
+            return;
+        }
+        let mut dir_entries = vec![(ROOT_INO, FileType::Directory, ".".to_string()), (ROOT_INO, FileType::Directory, "..".to_string())];
+        for (i, entry) in self.entries.iter().enumerate() {
+            dir_entries.push((i as u64 + 2, FileType::RegularFile, entry.name.clone()));
+        }
+        for (i, (ino, kind, name)) in dir_entries.into_iter().enumerate().skip(offset as usize) {
+            if reply.add(ino, (i + 1) as i64, kind, &name) {
+                break;
+            }
+        }
+        reply.ok();
+    }
+
+    fn open(&mut self, _req: &Request, ino: u64, _flags: i32, reply: fuser::ReplyOpen) {
+        if self.entry_for_ino(ino).is_none() {
+            reply.error(libc::ENOENT);
+            return;
+        }
+        // Pre-warm the content key so the first read() isn't the one
+        // paying for the RSA-OAEP unwrap.
+        let _ = self.content_key(ino);
+        reply.opened(0, 0);
+    }
+
+    fn read(&mut self, _req: &Request, ino: u64, _fh: u64, offset: i64, size: u32, _flags: i32, _lock: Option<u64>, reply: ReplyData) {
+        match self.read_range(ino, offset as u64, size) {
+            Some(data) => reply.data(&data),
+            None => reply.error(libc::EIO),
+        }
+    }
+}
+
+/// Mounts `ciphertexts_dir` read-only at `mountpoint`, decrypting `.rsa`
+/// bodies lazily on read. The returned session unmounts automatically when
+/// dropped (see `fuser::BackgroundSession`); `on_unmount_decrypted` in
+/// `main.rs` just drops it explicitly instead of waiting for shutdown.
+pub fn mount_decrypted(rsa_engine: RSAEngine, ciphertexts_dir: &Path, mountpoint: &Path) -> std::io::Result<fuser::BackgroundSession> {
+    let fs = DecryptedFs::new(rsa_engine, ciphertexts_dir)?;
+    let options = vec![fuser::MountOption::RO, fuser::MountOption::FSName("rsa-py-gui".to_string())];
+    fuser::spawn_mount2(fs, mountpoint, &options)
+}
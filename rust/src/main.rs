@@ -7,41 +7,343 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
+use argon2::{Algorithm, Argon2, Params, Version};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
-use openssl::rsa::Rsa;
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::rsa::{Rsa, RsaPrivateKeyBuilder};
+use openssl::sign::{Signer, Verifier};
+use openssl::symm::{Cipher, Crypter, Mode};
 use rand::RngCore;
 
+#[cfg(target_os = "linux")]
+mod fuse_mount;
+
 slint::include_modules!();
 
 // Constants for file encryption
 const RSAF_MAGIC: &[u8; 4] = b"RSAF";
-const RSAF_VERSION: u16 = 1;
+const RSAF_VERSION: u16 = 2;
 const MAX_ENCRYPT_PER_BLOCK: usize = 190;
 const ENCRYPTED_BLOCK_SIZE: usize = 256;
 const FILE_HEADER_SIZE: usize = 32;
 
-struct RSAEngine {
+// Hybrid envelope encryption (v2): a random AES-256-GCM content key is
+// generated per file and wrapped with RSA-OAEP; the body is streamed
+// through AES-GCM instead of being chopped into RSA blocks.
+const AES_KEY_SIZE: usize = 32;
+const AES_NONCE_SIZE: usize = 12;
+const AES_TAG_SIZE: usize = 16;
+// An RSA-2048 OAEP-wrapped AES-256 key fits in one 256-byte RSA block.
+const WRAPPED_KEY_SIZE: usize = ENCRYPTED_BLOCK_SIZE;
+const ENVELOPE_CHUNK_SIZE: usize = 64 * 1024;
+// Whole-file HMAC-SHA256, keyed with the content key, appended after the
+// last chunk. Per-chunk AEAD tags already prove each chunk wasn't modified,
+// but this trailer additionally catches truncation or chunk reordering
+// during a `verify_file` audit, which checks every chunk's tag and this
+// trailer without writing any plaintext out. `decrypt_file` does not read
+// or check the trailer.
+const FILE_MAC_SIZE: usize = 32;
+// The recipient fingerprint occupies the first 8 of the 12 reserved RSAF
+// header bytes; the next 2 hold the compression algorithm id and level
+// (see `encrypt_file`), leaving 2 bytes reserved for future use.
+const RECIPIENT_FINGERPRINT_SIZE: usize = 8;
+
+// Saved text-ciphertext ".bin" files: a small self-describing wrapper so the
+// recipient key can be auto-selected on load, same idea as the RSAF header.
+const CIPHERTEXT_BIN_MAGIC: &[u8; 4] = b"RSCB";
+const CIPHERTEXT_BIN_VERSION: u16 = 1;
+
+// Optional zstd compression pass applied to the file body before the AEAD
+// cipher runs (compression after encryption gains nothing, since ciphertext
+// looks random). The algorithm id lives in one of the header's reserved
+// bytes so `validate_rsaf_file` can report it.
+const COMPRESSION_NONE: u8 = 0;
+const COMPRESSION_ZSTD: u8 = 1;
+
+/// User-selected compression settings for the next `encrypt_file` call.
+#[derive(Clone, Copy)]
+struct CompressionSettings {
+    algo: u8,
+    level: i32,
+}
+
+impl Default for CompressionSettings {
+    fn default() -> Self {
+        Self { algo: COMPRESSION_NONE, level: 3 }
+    }
+}
+
+// Passphrase-protected private key container: an Argon2id-derived AES-256-GCM
+// wrapper around the PEM bytes, so a stolen .pem file alone isn't an identity.
+const KEYFILE_MAGIC: &[u8; 4] = b"RSPP";
+const KEYFILE_VERSION: u16 = 1;
+const ARGON2_SALT_SIZE: usize = 16;
+
+struct Argon2Params {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        // Tuned for an interactive desktop app: slow enough to resist
+        // offline guessing, fast enough not to stall the UI thread.
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+fn derive_key_argon2(passphrase: &str, salt: &[u8], params: &Argon2Params) -> Result<Vec<u8>, Box<dyn Error>> {
+    let argon2_params = Params::new(params.memory_kib, params.iterations, params.parallelism, Some(AES_KEY_SIZE))
+        .map_err(|e| format!("Invalid Argon2 parameters: {}", e))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params);
+    let mut key = vec![0u8; AES_KEY_SIZE];
+    argon2.hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+// Deterministic "brain key" RSA generation: a passphrase plus a displayed
+// salt are stretched through Argon2id with a fixed, deliberately expensive
+// cost (independent of `derive_key_argon2`, whose output length is
+// hardcoded to an AES key size) into a large seed, which is then expanded
+// into two prime candidates. As long as the passphrase and salt are written
+// down, the keypair can be regenerated byte-for-byte without the key file.
+const BRAIN_KEY_MEMORY_KIB: u32 = 1024 * 1024; // 1 GiB
+const BRAIN_KEY_ITERATIONS: u32 = 8;
+const BRAIN_KEY_PARALLELISM: u32 = 4;
+const BRAIN_KEY_SEED_SIZE: usize = 64;
+const BRAIN_KEY_MAX_ATTEMPTS: u32 = 100_000;
+
+/// Stretch `(passphrase, salt)` into a large, deterministic seed via a
+/// fixed, expensive Argon2id pass so brute-forcing the passphrase offline
+/// is as costly as generating the real key.
+fn derive_brain_seed(passphrase: &str, salt: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let params = Params::new(BRAIN_KEY_MEMORY_KIB, BRAIN_KEY_ITERATIONS, BRAIN_KEY_PARALLELISM, Some(BRAIN_KEY_SEED_SIZE))
+        .map_err(|e| format!("Invalid Argon2 parameters: {}", e))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+    let mut seed = vec![0u8; BRAIN_KEY_SEED_SIZE];
+    argon2.hash_password_into(passphrase.as_bytes(), salt, &mut seed)
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    Ok(seed)
+}
+
+/// Deterministically derive a `bits`-long prime from `seed`. `index`
+/// distinguishes `p` from `q` so they don't come out identical. The seed is
+/// expanded with a counter-mode SHA-256 stream into a candidate of the
+/// right size (top two bits and the low bit forced, as OpenSSL's own prime
+/// generation does), then walked upward by 2 until it tests prime.
+fn derive_prime_from_seed(seed: &[u8], index: u8, bits: u32) -> Result<BigNum, Box<dyn Error>> {
+    let byte_len = (bits as usize + 7) / 8;
+    let mut candidate_bytes = vec![0u8; byte_len];
+    let mut counter: u32 = 0;
+    let mut filled = 0;
+    while filled < byte_len {
+        let mut block = Vec::with_capacity(seed.len() + 5);
+        block.extend_from_slice(seed);
+        block.push(index);
+        block.extend_from_slice(&counter.to_le_bytes());
+        let digest = openssl::hash::hash(MessageDigest::sha256(), &block)?;
+        let take = (byte_len - filled).min(digest.len());
+        candidate_bytes[filled..filled + take].copy_from_slice(&digest[..take]);
+        filled += take;
+        counter += 1;
+    }
+
+    let mut candidate = BigNum::from_slice(&candidate_bytes)?;
+    candidate.set_bit(bits as i32 - 1)?;
+    candidate.set_bit(bits as i32 - 2)?;
+    candidate.set_bit(0)?;
+
+    let mut ctx = BigNumContext::new()?;
+    let two = BigNum::from_u32(2)?;
+    for _ in 0..BRAIN_KEY_MAX_ATTEMPTS {
+        if candidate.is_prime(64, &mut ctx)? {
+            return Ok(candidate);
+        }
+        candidate = &candidate + &two;
+    }
+    Err("Could not find a prime candidate from this seed; try a different salt".into())
+}
+
+/// One named identity in the keyring: its display name, the file it was
+/// loaded from or will be saved to, and the key material itself.
+#[derive(Clone)]
+struct KeyEntry {
+    key_name: String,
+    path: PathBuf,
     private_key: Option<Vec<u8>>,
     public_key: Option<Vec<u8>>,
 }
 
+/// SHA-256 fingerprint of a DER-encoded public key, truncated to 8 bytes
+/// (16 hex chars) so keys of the same size can still be told apart.
+fn public_key_fingerprint(public_key_pem: &[u8]) -> Result<[u8; 8], Box<dyn Error>> {
+    let rsa = Rsa::public_key_from_pem(public_key_pem)?;
+    let der = rsa.public_key_to_der()?;
+    let digest = openssl::hash::hash(MessageDigest::sha256(), &der)?;
+    let mut fp = [0u8; 8];
+    fp.copy_from_slice(&digest[..8]);
+    Ok(fp)
+}
+
+#[derive(Clone)]
+struct RSAEngine {
+    keys: Vec<KeyEntry>,
+    active_key: Option<usize>,
+}
+
 impl RSAEngine {
     fn new() -> Self {
         Self {
-            private_key: None,
-            public_key: None,
+            keys: Vec::new(),
+            active_key: None,
         }
     }
 
-    fn generate_keys(&mut self) -> Result<(), Box<dyn Error>> {
-        let rsa = Rsa::generate(2048)?;
-        self.private_key = Some(rsa.private_key_to_pem()?);
-        self.public_key = Some(rsa.public_key_to_pem()?);
+    fn active(&self) -> Option<&KeyEntry> {
+        self.active_key.and_then(|i| self.keys.get(i))
+    }
+
+    /// Insert or replace a named keyring entry and make it the active key.
+    fn upsert_key(&mut self, name: &str, path: PathBuf, private_key: Option<Vec<u8>>, public_key: Option<Vec<u8>>) {
+        if let Some(idx) = self.keys.iter().position(|k| k.key_name == name) {
+            if private_key.is_some() {
+                self.keys[idx].private_key = private_key;
+            }
+            if public_key.is_some() {
+                self.keys[idx].public_key = public_key;
+            }
+            self.keys[idx].path = path;
+            self.active_key = Some(idx);
+        } else {
+            self.keys.push(KeyEntry { key_name: name.to_string(), path, private_key, public_key });
+            self.active_key = Some(self.keys.len() - 1);
+        }
+    }
+
+    /// List keyring entries as (name, fingerprint) pairs for display.
+    fn list_keys(&self) -> Vec<(String, String)> {
+        self.keys.iter().map(|k| {
+            let fingerprint = k.public_key.as_ref()
+                .and_then(|pem| public_key_fingerprint(pem).ok())
+                .map(|fp| hex::encode(fp))
+                .unwrap_or_default();
+            (k.key_name.clone(), fingerprint)
+        }).collect()
+    }
+
+    fn select_key(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        let idx = self.keys.iter().position(|k| k.key_name == name)
+            .ok_or_else(|| format!("No such key: {}", name))?;
+        self.active_key = Some(idx);
+        Ok(())
+    }
+
+    /// Fingerprint of the active key's public key, if one is loaded.
+    fn active_fingerprint(&self) -> Option<[u8; RECIPIENT_FINGERPRINT_SIZE]> {
+        self.active()
+            .and_then(|k| k.public_key.as_ref())
+            .and_then(|pem| public_key_fingerprint(pem).ok())
+    }
+
+    /// Make the keyring entry whose public key matches `fingerprint` active,
+    /// so a ciphertext can be decrypted without the user hunting for the
+    /// right key by hand. Returns the name of the key it selected.
+    fn select_by_fingerprint(&mut self, fingerprint: &[u8; RECIPIENT_FINGERPRINT_SIZE]) -> Option<String> {
+        let idx = self.keys.iter().position(|k| {
+            k.public_key.as_ref()
+                .and_then(|pem| public_key_fingerprint(pem).ok())
+                .map_or(false, |fp| &fp == fingerprint)
+        })?;
+        self.active_key = Some(idx);
+        Some(self.keys[idx].key_name.clone())
+    }
+
+    /// Remove a key from the keyring and delete its backing file.
+    fn delete_key(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        let idx = self.keys.iter().position(|k| k.key_name == name)
+            .ok_or_else(|| format!("No such key: {}", name))?;
+        let entry = self.keys.remove(idx);
+        if entry.path.exists() {
+            fs::remove_file(&entry.path)?;
+        }
+        self.active_key = match self.active_key {
+            Some(active) if active == idx => None,
+            Some(active) if active > idx => Some(active - 1),
+            other => other,
+        };
+        Ok(())
+    }
+
+    /// Generate a fresh `bits`-bit RSA keypair under `name`, replacing any
+    /// existing entry of the same name, and make it the active key.
+    fn generate_named_key(&mut self, name: &str, path: PathBuf, bits: u32) -> Result<(), Box<dyn Error>> {
+        let rsa = Rsa::generate(bits)?;
+        let private_key = Some(rsa.private_key_to_pem()?);
+        let public_key = Some(rsa.public_key_to_pem()?);
+        self.upsert_key(name, path, private_key, public_key);
         Ok(())
     }
 
+    fn generate_keys(&mut self) -> Result<(), Box<dyn Error>> {
+        let name = self.active().map(|k| k.key_name.clone()).unwrap_or_else(|| "default".to_string());
+        let path = self.active().map(|k| k.path.clone()).unwrap_or_default();
+        self.generate_named_key(&name, path, 2048)
+    }
+
+    /// Deterministically (re)generate a `bits`-bit RSA keypair under `name`
+    /// from `passphrase`. If `salt` is `None`, a fresh random salt is drawn;
+    /// either way the salt used is returned so the caller can show it to the
+    /// user — the passphrase alone is not enough to recover the key again.
+    fn generate_named_key_from_phrase(&mut self, name: &str, path: PathBuf, passphrase: &str, bits: u32, salt: Option<Vec<u8>>) -> Result<Vec<u8>, Box<dyn Error>> {
+        let salt = salt.unwrap_or_else(|| {
+            let mut s = vec![0u8; ARGON2_SALT_SIZE];
+            rand::thread_rng().fill_bytes(&mut s);
+            s
+        });
+
+        let seed = derive_brain_seed(passphrase, &salt)?;
+        let prime_bits = bits / 2;
+        let p = derive_prime_from_seed(&seed, 0, prime_bits)?;
+        let q = derive_prime_from_seed(&seed, 1, prime_bits)?;
+
+        let mut ctx = BigNumContext::new()?;
+        let e = BigNum::from_u32(65537)?;
+        let one = BigNum::from_u32(1)?;
+        let p_minus_1 = &p - &one;
+        let q_minus_1 = &q - &one;
+        let mut phi = BigNum::new()?;
+        phi.checked_mul(&p_minus_1, &q_minus_1, &mut ctx)?;
+        let d = e.mod_inverse(&phi, &mut ctx)?;
+        let mut n = BigNum::new()?;
+        n.checked_mul(&p, &q, &mut ctx)?;
+
+        let mut dmp1 = BigNum::new()?;
+        dmp1.checked_rem(&d, &p_minus_1, &mut ctx)?;
+        let mut dmq1 = BigNum::new()?;
+        dmq1.checked_rem(&d, &q_minus_1, &mut ctx)?;
+        let iqmp = q.mod_inverse(&p, &mut ctx)?;
+
+        let rsa = RsaPrivateKeyBuilder::new(n, e, d)?
+            .set_factors(p, q)?
+            .set_crt_params(dmp1, dmq1, iqmp)?
+            .build();
+
+        let private_key = Some(rsa.private_key_to_pem()?);
+        let public_key = Some(rsa.public_key_to_pem()?);
+        self.upsert_key(name, path, private_key, public_key);
+        Ok(salt)
+    }
+
     fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
-        let pub_pem = self.public_key.as_ref()
+        let pub_pem = self.active().and_then(|k| k.public_key.as_ref())
             .ok_or("Keys not generated. Call generate_keys() first.")?;
         let rsa = Rsa::public_key_from_pem(pub_pem)?;
         let size = rsa.size() as usize;
@@ -52,7 +354,7 @@ impl RSAEngine {
     }
 
     fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
-        let priv_pem = self.private_key.as_ref()
+        let priv_pem = self.active().and_then(|k| k.private_key.as_ref())
             .ok_or("Keys not generated. Call generate_keys() first.")?;
         let rsa = Rsa::private_key_from_pem(priv_pem)?;
         let size = rsa.size() as usize;
@@ -62,15 +364,80 @@ impl RSAEngine {
         Ok(decrypted)
     }
 
+    /// Sign `data` with the loaded private key: hash with SHA-256 and sign
+    /// using RSA-PSS, proving authorship/integrity rather than confidentiality.
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let priv_pem = self.active().and_then(|k| k.private_key.as_ref())
+            .ok_or("Keys not generated. Call generate_keys() first.")?;
+        let rsa = Rsa::private_key_from_pem(priv_pem)?;
+        let pkey = PKey::from_rsa(rsa)?;
+        let mut signer = Signer::new(MessageDigest::sha256(), &pkey)?;
+        signer.set_rsa_padding(openssl::rsa::Padding::PKCS1_PSS)?;
+        signer.update(data)?;
+        Ok(signer.sign_to_vec()?)
+    }
+
+    /// Verify `sig` against `data` using the loaded public key.
+    fn verify(&self, data: &[u8], sig: &[u8]) -> Result<bool, Box<dyn Error>> {
+        let pub_pem = self.active().and_then(|k| k.public_key.as_ref())
+            .ok_or("Keys not generated. Call generate_keys() first.")?;
+        let rsa = Rsa::public_key_from_pem(pub_pem)?;
+        let pkey = PKey::from_rsa(rsa)?;
+        let mut verifier = Verifier::new(MessageDigest::sha256(), &pkey)?;
+        verifier.set_rsa_padding(openssl::rsa::Padding::PKCS1_PSS)?;
+        verifier.update(data)?;
+        Ok(verifier.verify(sig)?)
+    }
+
     fn save_private_key(&self, path: &Path) -> Result<(), Box<dyn Error>> {
-        let priv_pem = self.private_key.as_ref()
+        let priv_pem = self.active().and_then(|k| k.private_key.as_ref())
             .ok_or("Keys not generated. Call generate_keys() first.")?;
         fs::write(path, priv_pem)?;
         Ok(())
     }
 
+    /// Write the private key PEM encrypted under a passphrase: the PEM bytes
+    /// are AES-256-GCM-sealed with a key derived from `passphrase` via
+    /// Argon2id, and the salt/nonce/tag/KDF params are stored alongside the
+    /// ciphertext so the file is self-describing.
+    fn save_private_key_with_passphrase(&self, path: &Path, passphrase: &str) -> Result<(), Box<dyn Error>> {
+        let priv_pem = self.active().and_then(|k| k.private_key.as_ref())
+            .ok_or("Keys not generated. Call generate_keys() first.")?;
+
+        let params = Argon2Params::default();
+        let mut salt = vec![0u8; ARGON2_SALT_SIZE];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let key = derive_key_argon2(passphrase, &salt, &params)?;
+
+        let mut nonce = vec![0u8; AES_NONCE_SIZE];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let mut encrypter = Crypter::new(Cipher::aes_256_gcm(), Mode::Encrypt, &key, Some(&nonce))?;
+        let mut ciphertext = vec![0u8; priv_pem.len() + Cipher::aes_256_gcm().block_size()];
+        let mut written = encrypter.update(priv_pem, &mut ciphertext)?;
+        written += encrypter.finalize(&mut ciphertext[written..])?;
+        ciphertext.truncate(written);
+
+        let mut tag = [0u8; AES_TAG_SIZE];
+        encrypter.get_tag(&mut tag)?;
+
+        let mut container = Vec::with_capacity(4 + 2 + 12 + ARGON2_SALT_SIZE + AES_NONCE_SIZE + AES_TAG_SIZE + ciphertext.len());
+        container.extend_from_slice(KEYFILE_MAGIC);
+        container.extend_from_slice(&KEYFILE_VERSION.to_le_bytes());
+        container.extend_from_slice(&params.memory_kib.to_le_bytes());
+        container.extend_from_slice(&params.iterations.to_le_bytes());
+        container.extend_from_slice(&params.parallelism.to_le_bytes());
+        container.extend_from_slice(&salt);
+        container.extend_from_slice(&nonce);
+        container.extend_from_slice(&tag);
+        container.extend_from_slice(&ciphertext);
+
+        fs::write(path, container)?;
+        Ok(())
+    }
+
     fn save_public_key(&self, path: &Path) -> Result<(), Box<dyn Error>> {
-        let pub_pem = self.public_key.as_ref()
+        let pub_pem = self.active().and_then(|k| k.public_key.as_ref())
             .ok_or("Keys not generated. Call generate_keys() first.")?;
         fs::write(path, pub_pem)?;
         Ok(())
@@ -79,26 +446,89 @@ impl RSAEngine {
     fn load_private_key(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
         let bytes = fs::read(path)?;
         let _rsa = Rsa::private_key_from_pem(&bytes)?;
-        self.private_key = Some(bytes);
+        let name = key_name_for_path(path);
+        self.upsert_key(&name, path.to_path_buf(), Some(bytes), None);
+        Ok(())
+    }
+
+    /// Load a private key that may be a plain PEM or a passphrase-protected
+    /// container (detected via `KEYFILE_MAGIC`). A GCM tag mismatch against
+    /// a correctly-shaped container is reported as "wrong passphrase"
+    /// rather than a generic parse error.
+    fn load_private_key_with_passphrase(&mut self, path: &Path, passphrase: &str) -> Result<(), Box<dyn Error>> {
+        let bytes = fs::read(path)?;
+
+        if bytes.len() < 4 || &bytes[..4] != KEYFILE_MAGIC {
+            // Not a container; fall back to the plain-PEM path.
+            let _rsa = Rsa::private_key_from_pem(&bytes)?;
+            let name = key_name_for_path(path);
+            self.upsert_key(&name, path.to_path_buf(), Some(bytes), None);
+            return Ok(());
+        }
+
+        let mut offset = 4;
+        let version = u16::from_le_bytes(bytes[offset..offset + 2].try_into()?);
+        offset += 2;
+        if version != KEYFILE_VERSION {
+            return Err("Unsupported key container version".into());
+        }
+
+        let params = Argon2Params {
+            memory_kib: u32::from_le_bytes(bytes[offset..offset + 4].try_into()?),
+            iterations: u32::from_le_bytes(bytes[offset + 4..offset + 8].try_into()?),
+            parallelism: u32::from_le_bytes(bytes[offset + 8..offset + 12].try_into()?),
+        };
+        offset += 12;
+
+        let salt = &bytes[offset..offset + ARGON2_SALT_SIZE];
+        offset += ARGON2_SALT_SIZE;
+        let nonce = &bytes[offset..offset + AES_NONCE_SIZE];
+        offset += AES_NONCE_SIZE;
+        let tag = &bytes[offset..offset + AES_TAG_SIZE];
+        offset += AES_TAG_SIZE;
+        let ciphertext = &bytes[offset..];
+
+        let key = derive_key_argon2(passphrase, salt, &params)?;
+
+        let mut decrypter = Crypter::new(Cipher::aes_256_gcm(), Mode::Decrypt, &key, Some(nonce))?;
+        decrypter.set_tag(tag)?;
+        let mut plaintext = vec![0u8; ciphertext.len() + Cipher::aes_256_gcm().block_size()];
+        let mut written = decrypter.update(ciphertext, &mut plaintext)?;
+        written += decrypter.finalize(&mut plaintext[written..])
+            .map_err(|_| "Wrong passphrase")?;
+        plaintext.truncate(written);
+
+        let _rsa = Rsa::private_key_from_pem(&plaintext)?;
+        let name = key_name_for_path(path);
+        self.upsert_key(&name, path.to_path_buf(), Some(plaintext), None);
         Ok(())
     }
 
     fn load_public_key(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
         let bytes = fs::read(path)?;
         let _rsa = Rsa::public_key_from_pem(&bytes)?;
-        self.public_key = Some(bytes);
+        let name = key_name_for_path(path);
+        self.upsert_key(&name, path.to_path_buf(), None, Some(bytes));
         Ok(())
     }
 
     fn has_private_key(&self) -> bool {
-        self.private_key.is_some()
+        self.active().map_or(false, |k| k.private_key.is_some())
     }
 
     fn has_public_key(&self) -> bool {
-        self.public_key.is_some()
+        self.active().map_or(false, |k| k.public_key.is_some())
     }
 
-    fn encrypt_file<P: AsRef<Path>>(&self, src_path: P, dst_path: P, _progress_callback: impl Fn(u64, u64)) -> Result<(), Box<dyn Error>> {
+    /// Encrypt `src_path` into `dst_path` using hybrid envelope encryption:
+    /// a random AES-256-GCM content key is generated per file and wrapped
+    /// with RSA-OAEP, and the file body is split into independently
+    /// AEAD-sealed chunks. This keeps ciphertext size close to 1x plaintext
+    /// and lets tampering be detected chunk-by-chunk, unlike the old
+    /// per-block RSA/XOR-chaining scheme. If `compression` selects zstd, the
+    /// body is compressed before it is ever sealed, since compressing the
+    /// (effectively random) ciphertext afterwards would gain nothing.
+    fn encrypt_file<P: AsRef<Path>>(&self, src_path: P, dst_path: P, compression: CompressionSettings, progress_callback: impl Fn(u64, u64)) -> Result<(), Box<dyn Error>> {
         let src_path = src_path.as_ref();
         let dst_path = dst_path.as_ref();
 
@@ -115,63 +545,92 @@ impl RSAEngine {
             .ok_or("Invalid filename")?
             .to_string();
         let file_size = fs::metadata(src_path)?.len();
-        let block_count = (file_size as usize + MAX_ENCRYPT_PER_BLOCK - 1) / MAX_ENCRYPT_PER_BLOCK;
 
         let filename_bytes = filename.as_bytes();
-        let mut src_file = fs::File::open(src_path)?;
+        let src_file = fs::File::open(src_path)?;
         let mut dst_file = fs::File::create(dst_path)?;
 
-        // Write header
+        // Write header (block_count is unused by v2 and kept zeroed). The
+        // first 8 reserved bytes carry the recipient's public key
+        // fingerprint; of the remaining 4, one holds the compression
+        // algorithm id and one its level, leaving 2 bytes reserved.
+        let recipient_fingerprint = self.active_fingerprint().unwrap_or([0u8; RECIPIENT_FINGERPRINT_SIZE]);
         let mut header = Vec::with_capacity(FILE_HEADER_SIZE);
         header.extend_from_slice(RSAF_MAGIC);
         header.extend_from_slice(&RSAF_VERSION.to_le_bytes());
         header.extend_from_slice(&(filename_bytes.len() as u16).to_le_bytes());
         header.extend_from_slice(&file_size.to_le_bytes());
-        header.extend_from_slice(&(block_count as u32).to_le_bytes());
-        header.extend_from_slice(&[0u8; 12]); // Reserved
+        header.extend_from_slice(&0u32.to_le_bytes());
+        header.extend_from_slice(&recipient_fingerprint);
+        header.push(compression.algo);
+        header.push(compression.level.clamp(0, 22) as u8);
+        header.extend_from_slice(&[0u8; 2]); // Reserved
         dst_file.write_all(&header)?;
         dst_file.write_all(filename_bytes)?;
 
-        // Generate random IV and encrypt it
-        let mut iv = vec![0u8; MAX_ENCRYPT_PER_BLOCK];
-        rand::thread_rng().fill_bytes(&mut iv);
-        let iv_encrypted = self.encrypt(&iv)?;
-        dst_file.write_all(&iv_encrypted)?;
+        // Generate a random 256-bit content key and a random base nonce,
+        // then wrap the content key with RSA-OAEP into a single fixed-size
+        // field. The body is split into ENVELOPE_CHUNK_SIZE chunks, each
+        // AEAD-sealed independently (see `chunk_nonce`) with its own tag, so
+        // a reader can verify and decrypt chunk-by-chunk instead of needing
+        // the whole ciphertext in memory or trusting an unauthenticated tail.
+        let mut content_key = vec![0u8; AES_KEY_SIZE];
+        rand::thread_rng().fill_bytes(&mut content_key);
+        let mut base_nonce = vec![0u8; AES_NONCE_SIZE];
+        rand::thread_rng().fill_bytes(&mut base_nonce);
+        let wrapped_key = self.encrypt(&content_key)?;
+        if wrapped_key.len() != WRAPPED_KEY_SIZE {
+            return Err("Unexpected wrapped-key size for this RSA modulus".into());
+        }
+
+        dst_file.write_all(&wrapped_key)?;
+        dst_file.write_all(&base_nonce)?;
+        dst_file.write_all(&(ENVELOPE_CHUNK_SIZE as u32).to_le_bytes())?;
+
+        // Reading through a zstd encoder compresses on the fly, so the rest
+        // of this method doesn't need to know whether compression is on.
+        let mut reader: Box<dyn Read> = if compression.algo == COMPRESSION_ZSTD {
+            Box::new(zstd::stream::read::Encoder::new(src_file, compression.level)?)
+        } else {
+            Box::new(src_file)
+        };
 
-        // Encrypt file in CBC mode
-        let mut prev_ciphertext = iv_encrypted.clone();
+        let mut buf = vec![0u8; ENVELOPE_CHUNK_SIZE];
+        let mut out_buf = vec![0u8; ENVELOPE_CHUNK_SIZE + AES_TAG_SIZE];
         let mut bytes_processed: u64 = 0;
+        let mut chunk_index: u32 = 0;
+        let mac_key = PKey::hmac(&content_key)?;
+        let mut mac_signer = Signer::new(MessageDigest::sha256(), &mac_key)?;
 
-        let mut chunk = vec![0u8; MAX_ENCRYPT_PER_BLOCK];
         loop {
-            let bytes_read = src_file.read(&mut chunk)?;
+            let bytes_read = read_chunk(reader.as_mut(), &mut buf)?;
             if bytes_read == 0 {
                 break;
             }
 
-            let xored: Vec<u8> = if bytes_processed == 0 {
-                chunk[..bytes_read].iter().zip(iv.iter()).map(|(a, b)| a ^ b).collect()
-            } else {
-                chunk[..bytes_read].iter().zip(prev_ciphertext.iter()).map(|(a, b)| a ^ b).collect()
-            };
-
-            let mut xored_padded = xored;
-            if xored_padded.len() < MAX_ENCRYPT_PER_BLOCK {
-                xored_padded.extend(std::iter::repeat(0u8).take(MAX_ENCRYPT_PER_BLOCK - xored_padded.len()));
-            }
+            let nonce = chunk_nonce(&base_nonce, chunk_index);
+            let mut encrypter = Crypter::new(Cipher::aes_256_gcm(), Mode::Encrypt, &content_key, Some(&nonce))?;
+            let mut written = encrypter.update(&buf[..bytes_read], &mut out_buf)?;
+            written += encrypter.finalize(&mut out_buf[written..])?;
+            let mut tag = [0u8; AES_TAG_SIZE];
+            encrypter.get_tag(&mut tag)?;
 
-            let encrypted = self.encrypt(&xored_padded)?;
-            dst_file.write_all(&encrypted)?;
-            prev_ciphertext = encrypted;
+            dst_file.write_all(&out_buf[..written])?;
+            dst_file.write_all(&tag)?;
+            mac_signer.update(&out_buf[..written])?;
+            mac_signer.update(&tag)?;
 
             bytes_processed += bytes_read as u64;
-            _progress_callback(bytes_processed, file_size);
+            chunk_index += 1;
+            progress_callback(bytes_processed, file_size);
         }
 
+        dst_file.write_all(&mac_signer.sign_to_vec()?)?;
+
         Ok(())
     }
 
-    fn decrypt_file<P: AsRef<Path>>(&self, src_path: P, dst_path: P, _progress_callback: impl Fn(u64, u64)) -> Result<(), Box<dyn Error>> {
+    fn decrypt_file<P: AsRef<Path>>(&self, src_path: P, dst_path: P, progress_callback: impl Fn(u64, u64)) -> Result<(), Box<dyn Error>> {
         let src_path = src_path.as_ref();
         let dst_path = dst_path.as_ref();
 
@@ -184,21 +643,206 @@ impl RSAEngine {
         }
 
         let metadata = validate_rsaf_file(src_path)?;
+
+        if metadata.version == 1 {
+            return self.decrypt_file_v1(src_path, dst_path, &metadata, progress_callback);
+        }
+
         let file_size = metadata.file_size;
-        let block_count = metadata.block_count;
+        let chunk_size = metadata.chunk_size.ok_or("Missing chunk size in envelope header")? as usize;
+        let base_nonce = metadata.base_nonce.clone().ok_or("Missing base nonce in envelope header")?;
 
         let mut src_file = fs::File::open(src_path)?;
 
-        // Skip header and filename
+        let filename_bytes_len = metadata.filename.as_bytes().len();
+        let body_start = (FILE_HEADER_SIZE + filename_bytes_len + WRAPPED_KEY_SIZE + AES_NONCE_SIZE + 4) as u64;
+        src_file.seek(std::io::SeekFrom::Start((FILE_HEADER_SIZE + filename_bytes_len) as u64))?;
+
+        let mut wrapped_key = vec![0u8; WRAPPED_KEY_SIZE];
+        src_file.read_exact(&mut wrapped_key)?;
+        // base nonce and chunk size were already read out of the header by
+        // validate_rsaf_file; skip past them here.
+        src_file.seek(std::io::SeekFrom::Current((AES_NONCE_SIZE + 4) as i64))?;
+
+        let content_key = self.decrypt(&wrapped_key)?;
+
+        // The chunked ciphertext is followed by a whole-file MAC trailer
+        // (see `verify_file`); its size is derived from the on-disk file
+        // length rather than `file_size`, since that's the *original*
+        // (possibly pre-compression) length and not the ciphertext's.
+        let total_len = fs::metadata(src_path)?.len();
+        let body_len = total_len.saturating_sub(body_start).saturating_sub(FILE_MAC_SIZE as u64);
+
+        // Decrypt into a sibling temp file first: if a chunk fails its tag
+        // check partway through, the destination directory never ends up
+        // with truncated, unauthenticated plaintext.
+        let tmp_dst_path = dst_path.with_extension("rsa-partial");
+        let result = (|| -> Result<(), Box<dyn Error>> {
+            let mut dst_file = fs::File::create(&tmp_dst_path)?;
+            let mut buf = vec![0u8; chunk_size];
+            let mut out_buf = vec![0u8; chunk_size + AES_TAG_SIZE];
+            let mut bytes_processed: u64 = 0;
+            let mut chunk_index: u32 = 0;
+            let mut remaining = body_len;
+            // When the body was compressed, decrypting a chunk yields zstd
+            // frame bytes rather than the original file, so they're
+            // collected here and decompressed as a whole once every chunk
+            // is verified.
+            let mut decrypted = Vec::new();
+
+            while remaining > 0 {
+                // `remaining` always includes the chunk's trailing tag, so a
+                // file truncated mid-chunk can leave fewer than
+                // AES_TAG_SIZE bytes here; report that as corruption rather
+                // than underflowing the subtraction below.
+                let this_chunk_len = remaining.checked_sub(AES_TAG_SIZE as u64)
+                    .ok_or("integrity-failed: truncated chunk in ciphertext body")?
+                    .min(chunk_size as u64) as usize;
+                src_file.read_exact(&mut buf[..this_chunk_len])?;
+                let mut tag = [0u8; AES_TAG_SIZE];
+                src_file.read_exact(&mut tag)?;
+
+                let nonce = chunk_nonce(&base_nonce, chunk_index);
+                let mut decrypter = Crypter::new(Cipher::aes_256_gcm(), Mode::Decrypt, &content_key, Some(&nonce))?;
+                decrypter.set_tag(&tag)?;
+                let mut written = decrypter.update(&buf[..this_chunk_len], &mut out_buf)?;
+                // finalize() verifies this chunk's authentication tag and
+                // errors on mismatch, so a tampered chunk is caught before
+                // any more of the file is written out.
+                written += decrypter.finalize(&mut out_buf[written..])
+                    .map_err(|_| format!("integrity-failed: chunk {} was tampered with or corrupted", chunk_index))?;
+
+                if metadata.compression_algo == COMPRESSION_ZSTD {
+                    decrypted.extend_from_slice(&out_buf[..written]);
+                } else {
+                    dst_file.write_all(&out_buf[..written])?;
+                }
+
+                bytes_processed += this_chunk_len as u64;
+                remaining -= (this_chunk_len + AES_TAG_SIZE) as u64;
+                chunk_index += 1;
+                progress_callback(bytes_processed, file_size);
+            }
+
+            if metadata.compression_algo == COMPRESSION_ZSTD {
+                zstd::stream::copy_decode(&decrypted[..], &mut dst_file)
+                    .map_err(|e| format!("Decompression failed: {}", e))?;
+            }
+
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => {
+                fs::rename(&tmp_dst_path, dst_path)?;
+                Ok(())
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&tmp_dst_path);
+                Err(e)
+            }
+        }
+    }
+
+    /// Walk `src_path` chunk by chunk like `decrypt_file`, but discard the
+    /// plaintext instead of writing it out, and additionally recompute the
+    /// whole-file MAC trailer. Lets an archived `.rsa` file be audited for
+    /// bit-rot or tampering without touching the `decrypted/` directory.
+    fn verify_file<P: AsRef<Path>>(&self, src_path: P, progress_callback: impl Fn(u64, u64)) -> Result<(), Box<dyn Error>> {
+        let src_path = src_path.as_ref();
+
+        if !src_path.exists() {
+            return Err("Encrypted file not found".into());
+        }
+        if !self.has_private_key() {
+            return Err("Keys not generated".into());
+        }
+
+        let metadata = validate_rsaf_file(src_path)?;
+        if metadata.version == 1 {
+            return Err("Integrity verification is only available for v2+ envelope files".into());
+        }
+
+        let file_size = metadata.file_size;
+        let chunk_size = metadata.chunk_size.ok_or("Missing chunk size in envelope header")? as usize;
+        let base_nonce = metadata.base_nonce.clone().ok_or("Missing base nonce in envelope header")?;
+
+        let mut src_file = fs::File::open(src_path)?;
+        let filename_bytes_len = metadata.filename.as_bytes().len();
+        let body_start = (FILE_HEADER_SIZE + filename_bytes_len + WRAPPED_KEY_SIZE + AES_NONCE_SIZE + 4) as u64;
+        src_file.seek(std::io::SeekFrom::Start((FILE_HEADER_SIZE + filename_bytes_len) as u64))?;
+
+        let mut wrapped_key = vec![0u8; WRAPPED_KEY_SIZE];
+        src_file.read_exact(&mut wrapped_key)?;
+        src_file.seek(std::io::SeekFrom::Current((AES_NONCE_SIZE + 4) as i64))?;
+
+        let content_key = self.decrypt(&wrapped_key)?;
+        let mac_key = PKey::hmac(&content_key)?;
+        let mut mac_signer = Signer::new(MessageDigest::sha256(), &mac_key)?;
+
+        let total_len = fs::metadata(src_path)?.len();
+        let body_len = total_len.saturating_sub(body_start).saturating_sub(FILE_MAC_SIZE as u64);
+
+        let mut buf = vec![0u8; chunk_size];
+        let mut out_buf = vec![0u8; chunk_size + AES_TAG_SIZE];
+        let mut bytes_processed: u64 = 0;
+        let mut chunk_index: u32 = 0;
+        let mut remaining = body_len;
+
+        while remaining > 0 {
+            // See decrypt_file's matching comment: `remaining` includes the
+            // trailing tag, so guard against a truncated final chunk
+            // underflowing this subtraction.
+            let this_chunk_len = remaining.checked_sub(AES_TAG_SIZE as u64)
+                .ok_or("integrity-failed: truncated chunk in ciphertext body")?
+                .min(chunk_size as u64) as usize;
+            src_file.read_exact(&mut buf[..this_chunk_len])?;
+            let mut tag = [0u8; AES_TAG_SIZE];
+            src_file.read_exact(&mut tag)?;
+
+            let nonce = chunk_nonce(&base_nonce, chunk_index);
+            let mut decrypter = Crypter::new(Cipher::aes_256_gcm(), Mode::Decrypt, &content_key, Some(&nonce))?;
+            decrypter.set_tag(&tag)?;
+            let written = decrypter.update(&buf[..this_chunk_len], &mut out_buf)?;
+            // Plaintext is discarded; only the chunk's authenticity matters here.
+            decrypter.finalize(&mut out_buf[written..])
+                .map_err(|_| format!("integrity-failed: chunk {} was tampered with or corrupted", chunk_index))?;
+
+            mac_signer.update(&buf[..this_chunk_len])?;
+            mac_signer.update(&tag)?;
+
+            bytes_processed += this_chunk_len as u64;
+            remaining -= (this_chunk_len + AES_TAG_SIZE) as u64;
+            chunk_index += 1;
+            progress_callback(bytes_processed, file_size);
+        }
+
+        let mut stored_mac = vec![0u8; FILE_MAC_SIZE];
+        src_file.read_exact(&mut stored_mac)?;
+        let computed_mac = mac_signer.sign_to_vec()?;
+        if computed_mac != stored_mac {
+            return Err("integrity-failed: whole-file MAC mismatch (truncated or reordered chunks)".into());
+        }
+
+        Ok(())
+    }
+
+    /// Compatibility path for files written by the old v1 per-block RSA
+    /// "CBC" cipher, kept so previously encrypted files remain readable.
+    fn decrypt_file_v1<P: AsRef<Path>>(&self, src_path: P, dst_path: P, metadata: &RsaFileMetadata, _progress_callback: impl Fn(u64, u64)) -> Result<(), Box<dyn Error>> {
+        let src_path = src_path.as_ref();
+        let dst_path = dst_path.as_ref();
+        let file_size = metadata.file_size;
+        let block_count = metadata.block_count;
+
+        let mut src_file = fs::File::open(src_path)?;
         let filename_bytes_len = metadata.filename.as_bytes().len();
         src_file.seek(std::io::SeekFrom::Start((FILE_HEADER_SIZE + filename_bytes_len) as u64))?;
 
-        // Read and decrypt IV block
         let mut iv_encrypted = vec![0u8; ENCRYPTED_BLOCK_SIZE];
         src_file.read_exact(&mut iv_encrypted)?;
         let iv = self.decrypt(&iv_encrypted)?;
 
-        // Decrypt in CBC mode
         let mut dst_file = fs::File::create(dst_path)?;
         let mut prev_ciphertext = iv_encrypted;
         let mut bytes_processed: u64 = 0;
@@ -238,6 +882,15 @@ struct RsaFileMetadata {
     filename: String,
     file_size: u64,
     block_count: usize,
+    /// Recipient public key fingerprint embedded by v2+ writers, if any.
+    recipient_fingerprint: Option<[u8; RECIPIENT_FINGERPRINT_SIZE]>,
+    /// Random base nonce the envelope's per-chunk nonces are derived from (v2+ only).
+    base_nonce: Option<Vec<u8>>,
+    /// AEAD chunk size used to split the body (v2+ only).
+    chunk_size: Option<u32>,
+    /// Compression algorithm the body was run through before sealing
+    /// (`COMPRESSION_NONE` for v1 files and v2 files written without it).
+    compression_algo: u8,
 }
 
 fn validate_rsaf_file(filepath: &Path) -> Result<RsaFileMetadata, Box<dyn Error>> {
@@ -251,7 +904,7 @@ fn validate_rsaf_file(filepath: &Path) -> Result<RsaFileMetadata, Box<dyn Error>
     }
 
     let version = u16::from_le_bytes(header[4..6].try_into()?);
-    if version != RSAF_VERSION {
+    if version != RSAF_VERSION && version != 1 {
         return Err("Unsupported RSAF version".into());
     }
 
@@ -259,22 +912,91 @@ fn validate_rsaf_file(filepath: &Path) -> Result<RsaFileMetadata, Box<dyn Error>
     let file_size = u64::from_le_bytes(header[8..16].try_into()?);
     let block_count = u32::from_le_bytes(header[16..20].try_into()?) as usize;
 
+    let fingerprint_bytes = &header[20..20 + RECIPIENT_FINGERPRINT_SIZE];
+    let recipient_fingerprint = if version >= 2 && fingerprint_bytes.iter().any(|&b| b != 0) {
+        let mut fp = [0u8; RECIPIENT_FINGERPRINT_SIZE];
+        fp.copy_from_slice(fingerprint_bytes);
+        Some(fp)
+    } else {
+        None
+    };
+
+    let compression_offset = 20 + RECIPIENT_FINGERPRINT_SIZE;
+    let compression_algo = if version >= 2 { header[compression_offset] } else { COMPRESSION_NONE };
+
     let mut filename_bytes = vec![0u8; filename_len];
     file.read_exact(&mut filename_bytes)?;
     let filename = String::from_utf8_lossy(&filename_bytes).into_owned();
 
+    // v2+ bodies open with the RSA-wrapped content key, the envelope's base
+    // nonce, and the AEAD chunk size; surface the latter two so tooling (and
+    // the decrypt path) doesn't need to re-derive them.
+    let (base_nonce, chunk_size) = if version >= 2 {
+        let mut wrapped_key = vec![0u8; WRAPPED_KEY_SIZE];
+        file.read_exact(&mut wrapped_key)?;
+        let mut nonce = vec![0u8; AES_NONCE_SIZE];
+        file.read_exact(&mut nonce)?;
+        let mut chunk_size_bytes = [0u8; 4];
+        file.read_exact(&mut chunk_size_bytes)?;
+        (Some(nonce), Some(u32::from_le_bytes(chunk_size_bytes)))
+    } else {
+        (None, None)
+    };
+
     Ok(RsaFileMetadata {
         version,
         filename,
         file_size,
         block_count,
+        recipient_fingerprint,
+        base_nonce,
+        chunk_size,
+        compression_algo,
     })
 }
 
+/// Read up to `buf.len()` bytes, looping until the buffer is full or EOF is
+/// reached. A plain `Read::read` can return short reads before EOF, which
+/// would otherwise split a chunk across two AEAD-sealed pieces.
+fn read_chunk(reader: &mut dyn Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut total = 0;
+    while total < buf.len() {
+        let n = reader.read(&mut buf[total..])?;
+        if n == 0 {
+            break;
+        }
+        total += n;
+    }
+    Ok(total)
+}
+
+/// Derive the nonce for chunk `chunk_index` by XORing a little-endian
+/// counter into the low bytes of the envelope's random base nonce, so every
+/// chunk gets a unique nonce under the same content key without needing
+/// fresh randomness (or a nonce field) per chunk.
+fn chunk_nonce(base_nonce: &[u8], chunk_index: u32) -> Vec<u8> {
+    let mut nonce = base_nonce.to_vec();
+    let counter_bytes = chunk_index.to_le_bytes();
+    let offset = nonce.len() - counter_bytes.len();
+    for (i, b) in counter_bytes.iter().enumerate() {
+        nonce[offset + i] ^= b;
+    }
+    nonce
+}
+
 fn get_work_subdir(work_dir: &str, subdir: &str) -> PathBuf {
     PathBuf::from(work_dir).join(subdir)
 }
 
+/// Derive a keyring entry name from a key file's path (its stem), used when
+/// loading a key that wasn't given an explicit name.
+fn key_name_for_path(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("default")
+        .to_string()
+}
+
 fn format_file_size(size: u64) -> String {
     if size < 1024 {
         format!("{} B", size)
@@ -325,6 +1047,13 @@ const STRINGS: &[(&str, &str, &str)] = &[
     ("pub-loaded", "Public key loaded (encryption only)", "公钥已加载（仅可加密）"),
     ("no-keys", "Key files not found", "未找到密钥文件"),
     ("load-failed", "Load failed: ", "加载失败: "),
+    ("wrong-passphrase", "Wrong passphrase", "密码错误"),
+    ("sign-success", "Signed successfully", "签名成功"),
+    ("sign-failed", "Signing failed: ", "签名失败: "),
+    ("verify-ok", "OK Signature valid", "✓ 签名有效"),
+    ("verify-failed", "FAIL Signature invalid", "✗ 签名无效"),
+    ("verify-error", "Verification error: ", "验证错误: "),
+    ("key-deleted", "Key deleted", "密钥已删除"),
     ("loaded", "Loaded: ", "已加载: "),
     ("preview-no-keys", "Error: No keys. Generate or load keys first", "错误：无密钥，请先生成或加载密钥"),
     ("preview-ok", "OK Preview updated", "✓ 预览已更新"),
@@ -348,6 +1077,19 @@ const STRINGS: &[(&str, &str, &str)] = &[
     ("select-workdir", "Select work directory", "选择工作目录"),
     ("workdir-changed", "Work directory updated", "工作目录已更新"),
     ("workdir-invalid", "Invalid directory", "无效的目录"),
+    ("auto-selected-key", "Auto-selected key: ", "已自动选择密钥: "),
+    ("no-matching-key", "No matching key for fingerprint ", "没有与指纹匹配的密钥 "),
+    ("integrity-failed", "FAIL Integrity check failed: ", "✗ 完整性检查失败: "),
+    ("verifying", "Verifying...", "校验中..."),
+    ("integrity-ok", "OK All chunks authentic, no tampering detected", "✓ 所有分块均完整，未检测到篡改"),
+    ("mount-ok", "OK Mounted decrypted view at: ", "✓ 已挂载解密视图: "),
+    ("mount-failed", "FAIL Mount failed: ", "✗ 挂载失败: "),
+    ("already-mounted", "Already mounted; unmount first", "已挂载，请先卸载"),
+    ("unmount-ok", "OK Unmounted", "✓ 已卸载"),
+    ("not-mounted", "Nothing is mounted", "未挂载任何内容"),
+    ("mount-unsupported", "FAIL FUSE mount is only supported on Linux", "✗ FUSE 挂载仅支持 Linux"),
+    ("fingerprint", " (fingerprint: ", " (指纹: "),
+    ("brain-key-generated", "OK Brain key generated. Write down this salt, it is required to recover the key: ", "✓ 脑密钥已生成，请记下此盐值以便日后恢复: "),
 ];
 
 fn get_string(key: &str, language: usize) -> String {
@@ -358,6 +1100,75 @@ fn get_string(key: &str, language: usize) -> String {
         .to_string()
 }
 
+#[cfg(test)]
+mod envelope_tests {
+    use super::*;
+
+    fn unique_temp_path(name: &str) -> PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir().join(format!("rsa_py_gui_test_{}_{}", nanos, name))
+    }
+
+    fn engine_with_fresh_keys() -> RSAEngine {
+        let mut engine = RSAEngine::new();
+        engine.generate_named_key("test", PathBuf::new(), 2048).unwrap();
+        engine
+    }
+
+    fn roundtrip(message: &[u8]) {
+        let engine = engine_with_fresh_keys();
+        let src_path = unique_temp_path("src.bin");
+        let enc_path = unique_temp_path("enc.rsa");
+        let dst_path = unique_temp_path("dst.bin");
+
+        fs::write(&src_path, message).unwrap();
+        engine.encrypt_file(&src_path, &enc_path, CompressionSettings::default(), |_, _| {}).unwrap();
+        engine.decrypt_file(&enc_path, &dst_path, |_, _| {}).unwrap();
+
+        let decrypted = fs::read(&dst_path).unwrap();
+        assert_eq!(decrypted, message);
+
+        // A freshly encrypted file must also pass its own integrity check.
+        engine.verify_file(&enc_path, |_, _| {}).unwrap();
+
+        let _ = fs::remove_file(&src_path);
+        let _ = fs::remove_file(&enc_path);
+        let _ = fs::remove_file(&dst_path);
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip_small_file() {
+        roundtrip(b"Hello, encrypted world!");
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip_large_file_spans_multiple_chunks() {
+        // Exceeds ENVELOPE_CHUNK_SIZE (64 KiB) so the chunk-boundary
+        // accounting (ciphertext length vs. per-chunk tag) is actually
+        // exercised across more than one chunk.
+        let message = vec![0x5Au8; ENVELOPE_CHUNK_SIZE * 2 + 123];
+        roundtrip(&message);
+    }
+
+    #[test]
+    fn test_verify_file_on_intact_file_succeeds() {
+        let engine = engine_with_fresh_keys();
+        let src_path = unique_temp_path("verify_src.bin");
+        let enc_path = unique_temp_path("verify_enc.rsa");
+
+        fs::write(&src_path, b"verify me, a file of non-trivial size").unwrap();
+        engine.encrypt_file(&src_path, &enc_path, CompressionSettings::default(), |_, _| {}).unwrap();
+
+        engine.verify_file(&enc_path, |_, _| {}).unwrap();
+
+        let _ = fs::remove_file(&src_path);
+        let _ = fs::remove_file(&enc_path);
+    }
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let app_dir = std::env::current_exe()
         .map(|p| p.parent().unwrap_or(&p).to_path_buf())
@@ -365,6 +1176,9 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     let work_dir = Rc::new(RefCell::new(load_settings(&app_dir)));
     let language = Rc::new(RefCell::new(0usize));
+    let compression = Rc::new(RefCell::new(CompressionSettings::default()));
+    #[cfg(target_os = "linux")]
+    let mount_session: Rc<RefCell<Option<fuser::BackgroundSession>>> = Rc::new(RefCell::new(None));
 
     let rsa_engine = Rc::new(RefCell::new(RSAEngine::new()));
 
@@ -392,7 +1206,8 @@ fn main() -> Result<(), Box<dyn Error>> {
                 ui.set_status((get_string("gen-failed") + &e.to_string()).as_str().into());
             } else {
                 ui.set_has_keys(true);
-                ui.set_status(get_string("keys-generated").as_str().into());
+                let fingerprint = rsa_engine_clone.borrow().active_fingerprint().map(hex::encode).unwrap_or_default();
+                ui.set_status((get_string("keys-generated") + &get_string("fingerprint") + &fingerprint + ")").as_str().into());
             }
         });
     }
@@ -458,6 +1273,59 @@ fn main() -> Result<(), Box<dyn Error>> {
         });
     }
 
+    // Sign text
+    {
+        let ui_handle = ui.as_weak();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        ui.on_sign_text(move |plaintext| {
+            let ui = ui_handle.unwrap();
+            let plaintext = plaintext.as_str();
+            if plaintext.is_empty() {
+                return;
+            }
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+            match rsa_engine_clone.borrow().sign(plaintext.as_bytes()) {
+                Ok(signature) => {
+                    ui.set_signature(BASE64.encode(&signature).as_str().into());
+                    ui.set_status(get_string("sign-success").as_str().into());
+                }
+                Err(e) => {
+                    ui.set_status((get_string("sign-failed") + &e.to_string()).as_str().into());
+                }
+            }
+        });
+    }
+
+    // Verify text
+    {
+        let ui_handle = ui.as_weak();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        ui.on_verify_text(move |plaintext, signature_b64| {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+            match BASE64.decode(signature_b64.as_str()) {
+                Ok(signature) => {
+                    match rsa_engine_clone.borrow().verify(plaintext.as_str().as_bytes(), &signature) {
+                        Ok(true) => ui.set_status(get_string("verify-ok").as_str().into()),
+                        Ok(false) => ui.set_status(get_string("verify-failed").as_str().into()),
+                        Err(e) => ui.set_status((get_string("verify-error") + &e.to_string()).as_str().into()),
+                    }
+                }
+                Err(e) => {
+                    ui.set_status((get_string("verify-error") + &e.to_string()).as_str().into());
+                }
+            }
+        });
+    }
+
     // Save keys
     {
         let ui_handle = ui.as_weak();
@@ -517,10 +1385,206 @@ fn main() -> Result<(), Box<dyn Error>> {
         });
     }
 
+    // Save keys with passphrase protection
+    {
+        let ui_handle = ui.as_weak();
+        let work_dir_clone = work_dir.clone();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        ui.on_save_keys_with_passphrase(move |passphrase| {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+            let private_path = get_work_subdir(&work_dir_clone.borrow(), "private_key.pem");
+            let public_path = get_work_subdir(&work_dir_clone.borrow(), "public_key.pem");
+            let passphrase = passphrase.as_str();
+            let save_result = if passphrase.is_empty() {
+                rsa_engine_clone.borrow().save_private_key(&private_path)
+            } else {
+                rsa_engine_clone.borrow().save_private_key_with_passphrase(&private_path, passphrase)
+            };
+            if let Err(e) = save_result {
+                ui.set_status((get_string("save-failed") + &e.to_string()).as_str().into());
+            } else if let Err(e) = rsa_engine_clone.borrow().save_public_key(&public_path) {
+                ui.set_status((get_string("save-failed") + &e.to_string()).as_str().into());
+            } else {
+                ui.set_status(get_string("keys-saved").as_str().into());
+            }
+        });
+    }
+
+    // Load keys with passphrase (for passphrase-protected private keys)
+    {
+        let ui_handle = ui.as_weak();
+        let work_dir_clone = work_dir.clone();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        ui.on_load_keys_with_passphrase(move |passphrase| {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+            let private_path = get_work_subdir(&work_dir_clone.borrow(), "private_key.pem");
+            match rsa_engine_clone.borrow_mut().load_private_key_with_passphrase(&private_path, passphrase.as_str()) {
+                Ok(()) => {
+                    ui.set_has_keys(true);
+                    ui.set_status(get_string("keys-loaded").as_str().into());
+                }
+                Err(e) => {
+                    if e.to_string() == "Wrong passphrase" {
+                        ui.set_status(get_string("wrong-passphrase").as_str().into());
+                    } else {
+                        ui.set_status((get_string("load-failed") + &e.to_string()).as_str().into());
+                    }
+                }
+            }
+        });
+    }
+
+    // Generate a new named key of a selectable modulus size in the
+    // keyring, stored under keys/<name>.pem
+    {
+        let ui_handle = ui.as_weak();
+        let work_dir_clone = work_dir.clone();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        ui.on_generate_named_key(move |name, bits| {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+            let keys_dir = get_work_subdir(&work_dir_clone.borrow(), "keys");
+            let _ = std::fs::create_dir_all(&keys_dir);
+            let path = keys_dir.join(format!("{}.pem", name.as_str()));
+            if let Err(e) = rsa_engine_clone.borrow_mut().generate_named_key(name.as_str(), path, bits as u32) {
+                ui.set_status((get_string("gen-failed") + &e.to_string()).as_str().into());
+                return;
+            }
+            if let Some(entry) = rsa_engine_clone.borrow().active() {
+                if let Some(priv_pem) = &entry.private_key {
+                    let _ = fs::write(&entry.path, priv_pem);
+                }
+            }
+            ui.set_has_keys(true);
+            let fingerprint = rsa_engine_clone.borrow().active_fingerprint().map(hex::encode).unwrap_or_default();
+            ui.set_status((get_string("keys-generated") + &get_string("fingerprint") + &fingerprint + ")").as_str().into());
+        });
+    }
+
+    // Generate a named key from a passphrase ("brain key"): the same
+    // passphrase and salt always reproduce the same keypair. This handler
+    // always draws a fresh random salt, shows it in the status line and
+    // saves it as keys/<name>.salt; it takes no salt as input, so it does
+    // not regenerate an existing key.
+    {
+        let ui_handle = ui.as_weak();
+        let work_dir_clone = work_dir.clone();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        ui.on_generate_key_from_phrase(move |name, passphrase, bits| {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+            let keys_dir = get_work_subdir(&work_dir_clone.borrow(), "keys");
+            let _ = std::fs::create_dir_all(&keys_dir);
+            let path = keys_dir.join(format!("{}.pem", name.as_str()));
+            let salt = match rsa_engine_clone.borrow_mut().generate_named_key_from_phrase(name.as_str(), path, passphrase.as_str(), bits as u32, None) {
+                Ok(salt) => salt,
+                Err(e) => {
+                    ui.set_status((get_string("gen-failed") + &e.to_string()).as_str().into());
+                    return;
+                }
+            };
+            if let Some(entry) = rsa_engine_clone.borrow().active() {
+                if let Some(priv_pem) = &entry.private_key {
+                    let _ = fs::write(&entry.path, priv_pem);
+                }
+                let salt_path = keys_dir.join(format!("{}.salt", name.as_str()));
+                let _ = fs::write(&salt_path, hex::encode(&salt));
+            }
+            ui.set_has_keys(true);
+            let fingerprint = rsa_engine_clone.borrow().active_fingerprint().map(hex::encode).unwrap_or_default();
+            ui.set_status((get_string("brain-key-generated") + &hex::encode(&salt) + &get_string("fingerprint") + &fingerprint + ")").as_str().into());
+        });
+    }
+
+    // List keyring entries
+    {
+        let ui_handle = ui.as_weak();
+        let rsa_engine_clone = rsa_engine.clone();
+        ui.on_list_keys(move || {
+            let ui = ui_handle.unwrap();
+            let items: Vec<slint::StandardListViewItem> = rsa_engine_clone.borrow().list_keys()
+                .into_iter()
+                .map(|(name, fingerprint)| {
+                    let mut item = slint::StandardListViewItem::default();
+                    item.text = format!("{} ({})", name, fingerprint).into();
+                    item
+                })
+                .collect();
+            let model = Rc::new(slint::VecModel::from(items));
+            ui.set_key_items(model.into());
+        });
+    }
+
+    // Select the active key in the keyring
+    {
+        let ui_handle = ui.as_weak();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        ui.on_select_key(move |name| {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+            match rsa_engine_clone.borrow_mut().select_key(name.as_str()) {
+                Ok(()) => {
+                    ui.set_has_keys(true);
+                    let fingerprint = rsa_engine_clone.borrow().active_fingerprint().map(hex::encode).unwrap_or_default();
+                    ui.set_status((get_string("loaded") + name.as_str() + &get_string("fingerprint") + &fingerprint + ")").as_str().into());
+                }
+                Err(e) => {
+                    ui.set_status((get_string("load-failed") + &e.to_string()).as_str().into());
+                }
+            }
+        });
+    }
+
+    // Delete a key from the keyring (and its backing file)
+    {
+        let ui_handle = ui.as_weak();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        ui.on_delete_key(move |name| {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+            match rsa_engine_clone.borrow_mut().delete_key(name.as_str()) {
+                Ok(()) => {
+                    ui.set_has_keys(rsa_engine_clone.borrow().active().is_some());
+                    ui.set_status(get_string("key-deleted").as_str().into());
+                }
+                Err(e) => {
+                    ui.set_status((get_string("load-failed") + &e.to_string()).as_str().into());
+                }
+            }
+        });
+    }
+
     // Save ciphertext bin
     {
         let ui_handle = ui.as_weak();
         let work_dir_clone = work_dir.clone();
+        let rsa_engine_clone = rsa_engine.clone();
         let language_clone = language.clone();
         ui.on_save_ciphertext_bin(move || {
             let ui = ui_handle.unwrap();
@@ -540,7 +1604,17 @@ fn main() -> Result<(), Box<dyn Error>> {
                 let _ = std::fs::create_dir_all(&ciphertext_dir);
                 let filename = format!("{}.bin", hex::encode(&ciphertext_bytes[..10.min(ciphertext_bytes.len())]));
                 let filepath = ciphertext_dir.join(&filename);
-                let _ = std::fs::write(&filepath, &ciphertext_bytes);
+
+                // Remember which key this ciphertext was addressed to so it
+                // can be auto-selected again on load.
+                let fingerprint = rsa_engine_clone.borrow().active_fingerprint().unwrap_or([0u8; RECIPIENT_FINGERPRINT_SIZE]);
+                let mut container = Vec::with_capacity(4 + 2 + RECIPIENT_FINGERPRINT_SIZE + ciphertext_bytes.len());
+                container.extend_from_slice(CIPHERTEXT_BIN_MAGIC);
+                container.extend_from_slice(&CIPHERTEXT_BIN_VERSION.to_le_bytes());
+                container.extend_from_slice(&fingerprint);
+                container.extend_from_slice(&ciphertext_bytes);
+
+                let _ = std::fs::write(&filepath, &container);
                 ui.set_status((get_string("cipher-saved") + &filename).as_str().into());
             } else {
                 ui.set_status(get_string("save-failed").as_str().into());
@@ -583,6 +1657,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     {
         let ui_handle = ui.as_weak();
         let work_dir_clone = work_dir.clone();
+        let rsa_engine_clone = rsa_engine.clone();
         let language_clone = language.clone();
         ui.on_load_ciphertext_file(move |filename| {
             let ui = ui_handle.unwrap();
@@ -593,9 +1668,37 @@ fn main() -> Result<(), Box<dyn Error>> {
             let work_dir_borrow = work_dir_clone.borrow();
             let filepath = get_work_subdir(&work_dir_borrow, "ciphertexts").join(filename.as_str());
             match std::fs::read(&filepath) {
-                Ok(ciphertext) => {
+                Ok(bytes) => {
+                    // Older .bin files (saved before recipient addressing)
+                    // carry no magic; treat them as raw ciphertext.
+                    let (fingerprint, ciphertext) = if bytes.len() >= 6 + RECIPIENT_FINGERPRINT_SIZE
+                        && &bytes[..4] == CIPHERTEXT_BIN_MAGIC
+                    {
+                        let mut fp = [0u8; RECIPIENT_FINGERPRINT_SIZE];
+                        fp.copy_from_slice(&bytes[6..6 + RECIPIENT_FINGERPRINT_SIZE]);
+                        (Some(fp), bytes[6 + RECIPIENT_FINGERPRINT_SIZE..].to_vec())
+                    } else {
+                        (None, bytes)
+                    };
+
+                    let mut note = String::new();
+                    if let Some(fp) = fingerprint {
+                        if fp != [0u8; RECIPIENT_FINGERPRINT_SIZE] {
+                            let matches_active = rsa_engine_clone.borrow().active_fingerprint() == Some(fp);
+                            if !matches_active {
+                                match rsa_engine_clone.borrow_mut().select_by_fingerprint(&fp) {
+                                    Some(name) => note = format!("{}{}; ", get_string("auto-selected-key"), name),
+                                    None => {
+                                        ui.set_status((get_string("no-matching-key") + &hex::encode(fp)).as_str().into());
+                                        return;
+                                    }
+                                }
+                            }
+                        }
+                    }
+
                     ui.set_ciphertext(BASE64.encode(&ciphertext).as_str().into());
-                    ui.set_status((get_string("loaded") + filename.as_str()).as_str().into());
+                    ui.set_status((note + &get_string("loaded") + filename.as_str()).as_str().into());
                 }
                 Err(e) => {
                     ui.set_status((get_string("load-failed") + &e.to_string()).as_str().into());
@@ -680,6 +1783,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         let work_dir_clone = work_dir.clone();
         let rsa_engine_clone = rsa_engine.clone();
         let language_clone = language.clone();
+        let compression_clone = compression.clone();
         ui.on_encrypt_file(move || {
             let ui = ui_handle.unwrap();
             let lang = *language_clone.borrow();
@@ -714,7 +1818,8 @@ fn main() -> Result<(), Box<dyn Error>> {
             ui.set_progress_value(0.0);
             ui.set_file_status(get_string("encrypting").as_str().into());
 
-            if let Err(e) = rsa_engine_clone.borrow().encrypt_file(&src_path, &dst_path, |processed, total| {
+            let compression_settings = *compression_clone.borrow();
+            if let Err(e) = rsa_engine_clone.borrow().encrypt_file(&src_path, &dst_path, compression_settings, |processed, total| {
                 if total > 0 {
                     let progress = processed as f32 / total as f32;
                     ui.set_progress_value(progress as f32);
@@ -736,7 +1841,11 @@ fn main() -> Result<(), Box<dyn Error>> {
 
             ui.set_file_status((get_string("ok-output") + &dst_filename).as_str().into());
             ui.set_progress_value(1.0);
-            ui.set_file_progress(format!("Expansion: {:.2}x ({} -> {} bytes)", ratio, src_size, dst_size).as_str().into());
+            if compression_settings.algo == COMPRESSION_ZSTD {
+                ui.set_file_progress(format!("Compressed {} -> {} bytes, output {:.2}x ({} bytes)", src_size, dst_size, ratio, dst_size).as_str().into());
+            } else {
+                ui.set_file_progress(format!("Expansion: {:.2}x ({} -> {} bytes)", ratio, src_size, dst_size).as_str().into());
+            }
 
             // Refresh file list
             let rsa_dir = get_work_subdir(&work_dir_borrow, "ciphertexts");
@@ -789,13 +1898,27 @@ fn main() -> Result<(), Box<dyn Error>> {
             }
             let metadata = metadata.unwrap();
 
+            let mut status_prefix = String::new();
+            if let Some(fp) = metadata.recipient_fingerprint {
+                let matches_active = rsa_engine_clone.borrow().active_fingerprint() == Some(fp);
+                if !matches_active {
+                    match rsa_engine_clone.borrow_mut().select_by_fingerprint(&fp) {
+                        Some(name) => status_prefix = format!("{}{}; ", get_string("auto-selected-key"), name),
+                        None => {
+                            ui.set_file_status((get_string("no-matching-key") + &hex::encode(fp)).as_str().into());
+                            return;
+                        }
+                    }
+                }
+            }
+
             let work_dir_borrow = work_dir_clone.borrow();
             let output_dir = get_work_subdir(&work_dir_borrow, "decrypted");
             let _ = std::fs::create_dir_all(&output_dir);
             let dst_path = output_dir.join(&metadata.filename);
 
             ui.set_progress_value(0.0);
-            ui.set_file_status(get_string("decrypting").as_str().into());
+            ui.set_file_status((status_prefix.clone() + &get_string("decrypting")).as_str().into());
 
             if let Err(e) = rsa_engine_clone.borrow().decrypt_file(&src_path, &dst_path, |processed, total| {
                 if total > 0 {
@@ -803,17 +1926,233 @@ fn main() -> Result<(), Box<dyn Error>> {
                     ui.set_progress_value(progress as f32);
                 }
             }) {
-                ui.set_file_status((get_string("fail-dec") + &e.to_string()).as_str().into());
+                let msg = e.to_string();
+                if let Some(detail) = msg.strip_prefix("integrity-failed: ") {
+                    ui.set_file_status((get_string("integrity-failed") + detail).as_str().into());
+                } else {
+                    ui.set_file_status((get_string("fail-dec") + &msg).as_str().into());
+                }
                 ui.set_progress_value(0.0);
                 return;
             }
 
-            ui.set_file_status((get_string("ok-saved") + &metadata.filename).as_str().into());
+            ui.set_file_status((status_prefix + &get_string("ok-saved") + &metadata.filename).as_str().into());
             ui.set_progress_value(1.0);
             ui.set_file_progress(format!("File size: {} bytes", metadata.file_size).as_str().into());
         });
     }
 
+    // Verify file: audits a selected .rsa ciphertext for tampering or bit-rot
+    // without writing anything to the decrypted/ directory.
+    {
+        let ui_handle = ui.as_weak();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        ui.on_verify_file(move || {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+            let selected_cipher = ui.get_selected_cipher();
+            if selected_cipher.as_str().is_empty() {
+                ui.set_file_status(get_string("select-cipher-first").as_str().into());
+                return;
+            }
+
+            let src_path = PathBuf::from(selected_cipher.as_str());
+            if !src_path.exists() {
+                ui.set_file_status(get_string("select-cipher-first").as_str().into());
+                return;
+            }
+
+            ui.set_progress_value(0.0);
+            ui.set_file_status(get_string("verifying").as_str().into());
+
+            if let Err(e) = rsa_engine_clone.borrow().verify_file(&src_path, |processed, total| {
+                if total > 0 {
+                    let progress = processed as f32 / total as f32;
+                    ui.set_progress_value(progress as f32);
+                }
+            }) {
+                let msg = e.to_string();
+                if let Some(detail) = msg.strip_prefix("integrity-failed: ") {
+                    ui.set_file_status((get_string("integrity-failed") + detail).as_str().into());
+                } else {
+                    ui.set_file_status((get_string("fail-dec") + &msg).as_str().into());
+                }
+                ui.set_progress_value(0.0);
+                return;
+            }
+
+            ui.set_file_status(get_string("integrity-ok").as_str().into());
+            ui.set_progress_value(1.0);
+        });
+    }
+
+    // Mount decrypted view (Linux only): a read-only FUSE filesystem that
+    // decrypts each `.rsa` file in `ciphertexts/` lazily on read, so users
+    // can browse ciphertexts in any application without a bulk decrypt.
+    #[cfg(target_os = "linux")]
+    {
+        let ui_handle = ui.as_weak();
+        let work_dir_clone = work_dir.clone();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        let mount_session_clone = mount_session.clone();
+        ui.on_mount_decrypted(move || {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+
+            if mount_session_clone.borrow().is_some() {
+                ui.set_status(get_string("already-mounted").as_str().into());
+                return;
+            }
+
+            let work_dir_borrow = work_dir_clone.borrow();
+            let ciphertexts_dir = get_work_subdir(&work_dir_borrow, "ciphertexts");
+            let mountpoint = get_work_subdir(&work_dir_borrow, "mount");
+            let _ = std::fs::create_dir_all(&ciphertexts_dir);
+            let _ = std::fs::create_dir_all(&mountpoint);
+
+            let engine_snapshot = rsa_engine_clone.borrow().clone();
+            match fuse_mount::mount_decrypted(engine_snapshot, &ciphertexts_dir, &mountpoint) {
+                Ok(session) => {
+                    *mount_session_clone.borrow_mut() = Some(session);
+                    ui.set_status((get_string("mount-ok") + &mountpoint.to_string_lossy()).as_str().into());
+                }
+                Err(e) => {
+                    ui.set_status((get_string("mount-failed") + &e.to_string()).as_str().into());
+                }
+            }
+        });
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let ui_handle = ui.as_weak();
+        let language_clone = language.clone();
+        let mount_session_clone = mount_session.clone();
+        ui.on_unmount_decrypted(move || {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            if mount_session_clone.borrow_mut().take().is_some() {
+                ui.set_status(get_string("unmount-ok", lang).as_str().into());
+            } else {
+                ui.set_status(get_string("not-mounted", lang).as_str().into());
+            }
+        });
+    }
+
+    // Non-Linux builds still need both callbacks wired (the UI exposes the
+    // buttons unconditionally), just reporting that FUSE isn't available.
+    #[cfg(not(target_os = "linux"))]
+    {
+        let ui_handle = ui.as_weak();
+        let language_clone = language.clone();
+        ui.on_mount_decrypted(move || {
+            let ui = ui_handle.unwrap();
+            ui.set_status(get_string("mount-unsupported", *language_clone.borrow()).as_str().into());
+        });
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let ui_handle = ui.as_weak();
+        let language_clone = language.clone();
+        ui.on_unmount_decrypted(move || {
+            let ui = ui_handle.unwrap();
+            ui.set_status(get_string("mount-unsupported", *language_clone.borrow()).as_str().into());
+        });
+    }
+
+    // Sign file: writes a detached .sig sidecar next to the source file
+    {
+        let ui_handle = ui.as_weak();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        ui.on_sign_file(move || {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+            let selected_file = ui.get_selected_file();
+            if selected_file.as_str().is_empty() {
+                ui.set_file_status(get_string("select-file-first").as_str().into());
+                return;
+            }
+
+            let src_path = PathBuf::from(selected_file.as_str());
+            let data = match fs::read(&src_path) {
+                Ok(data) => data,
+                Err(e) => {
+                    ui.set_file_status((get_string("sign-failed") + &e.to_string()).as_str().into());
+                    return;
+                }
+            };
+
+            match rsa_engine_clone.borrow().sign(&data) {
+                Ok(signature) => {
+                    let sig_path = PathBuf::from(format!("{}.sig", src_path.to_string_lossy()));
+                    match fs::write(&sig_path, &signature) {
+                        Ok(()) => ui.set_file_status((get_string("sign-success") + &sig_path.to_string_lossy()).as_str().into()),
+                        Err(e) => ui.set_file_status((get_string("sign-failed") + &e.to_string()).as_str().into()),
+                    }
+                }
+                Err(e) => {
+                    ui.set_file_status((get_string("sign-failed") + &e.to_string()).as_str().into());
+                }
+            }
+        });
+    }
+
+    // Verify file: reads the detached .sig sidecar for the selected file
+    {
+        let ui_handle = ui.as_weak();
+        let rsa_engine_clone = rsa_engine.clone();
+        let language_clone = language.clone();
+        ui.on_verify_file(move || {
+            let ui = ui_handle.unwrap();
+            let lang = *language_clone.borrow();
+            let get_string = |key: &str| -> String {
+                get_string(key, lang)
+            };
+            let selected_file = ui.get_selected_file();
+            if selected_file.as_str().is_empty() {
+                ui.set_file_status(get_string("select-file-first").as_str().into());
+                return;
+            }
+
+            let src_path = PathBuf::from(selected_file.as_str());
+            let sig_path = PathBuf::from(format!("{}.sig", src_path.to_string_lossy()));
+
+            let data = match fs::read(&src_path) {
+                Ok(data) => data,
+                Err(e) => {
+                    ui.set_file_status((get_string("verify-error") + &e.to_string()).as_str().into());
+                    return;
+                }
+            };
+            let signature = match fs::read(&sig_path) {
+                Ok(sig) => sig,
+                Err(e) => {
+                    ui.set_file_status((get_string("verify-error") + &e.to_string()).as_str().into());
+                    return;
+                }
+            };
+
+            match rsa_engine_clone.borrow().verify(&data, &signature) {
+                Ok(true) => ui.set_file_status(get_string("verify-ok").as_str().into()),
+                Ok(false) => ui.set_file_status(get_string("verify-failed").as_str().into()),
+                Err(e) => ui.set_file_status((get_string("verify-error") + &e.to_string()).as_str().into()),
+            }
+        });
+    }
+
     // Set language
     {
         let language_clone = language.clone();
@@ -822,6 +2161,17 @@ fn main() -> Result<(), Box<dyn Error>> {
         });
     }
 
+    // Set compression
+    {
+        let compression_clone = compression.clone();
+        ui.on_set_compression(move |enabled, level| {
+            *compression_clone.borrow_mut() = CompressionSettings {
+                algo: if enabled { COMPRESSION_ZSTD } else { COMPRESSION_NONE },
+                level: level as i32,
+            };
+        });
+    }
+
     // Select work dir
     {
         let ui_handle = ui.as_weak();
@@ -3,7 +3,7 @@
 
 use num_bigint::{BigUint, RandBigInt, ToBigUint};
 use num_integer::Integer;
-use num_traits::{One, Zero, Pow};
+use num_traits::{One, Zero, Pow, ToPrimitive};
 use rand::thread_rng;
 use std::fmt;
 
@@ -47,6 +47,128 @@ pub fn mod_pow(base: &RsaBigInt, exp: &RsaBigInt, modulus: &RsaBigInt) -> RsaBig
     result
 }
 
+/// Montgomery arithmetic context for a fixed odd modulus.
+/// R = 2^r_bits, chosen as the smallest multiple of the word size (64 bits)
+/// that exceeds the modulus, so REDC can replace the per-step `% modulus`
+/// division used by the naive ladder in `mod_pow`.
+struct MontgomeryContext {
+    n: RsaBigInt,
+    r_bits: u32,
+    n_prime: RsaBigInt, // -n^(-1) mod R
+    one_mont: RsaBigInt, // R mod n, i.e. 1 in Montgomery form
+}
+
+impl MontgomeryContext {
+    const WORD_BITS: u32 = 64;
+
+    fn new(modulus: &RsaBigInt) -> Self {
+        let bits = modulus.bits().max(1) as u32;
+        let r_bits = ((bits + Self::WORD_BITS - 1) / Self::WORD_BITS) * Self::WORD_BITS;
+        let r = RsaBigInt::one() << r_bits;
+
+        let n_inv_mod_r = mod_inverse(&(modulus % &r), &r)
+            .expect("modulus must be odd to have a Montgomery inverse mod R");
+        let n_prime = &r - n_inv_mod_r;
+        let one_mont = &r % modulus;
+
+        MontgomeryContext {
+            n: modulus.clone(),
+            r_bits,
+            n_prime,
+            one_mont,
+        }
+    }
+
+    fn r_mask(&self) -> RsaBigInt {
+        (RsaBigInt::one() << self.r_bits) - 1u8
+    }
+
+    /// Convert a value into Montgomery form: a * R mod n
+    fn to_mont(&self, a: &RsaBigInt) -> RsaBigInt {
+        (a.clone() << self.r_bits) % &self.n
+    }
+
+    /// Montgomery REDC: reduce t (< n * R) to t * R^(-1) mod n
+    fn redc(&self, t: &RsaBigInt) -> RsaBigInt {
+        let mask = self.r_mask();
+        let m = &(&(t & &mask) * &self.n_prime) & &mask;
+        let m_n = &m * &self.n;
+        let combined = (t + &m_n) >> self.r_bits;
+        if combined >= self.n {
+            combined - &self.n
+        } else {
+            combined
+        }
+    }
+
+    /// Multiply two Montgomery-form values, returning a Montgomery-form result
+    fn mul(&self, a: &RsaBigInt, b: &RsaBigInt) -> RsaBigInt {
+        self.redc(&(a * b))
+    }
+
+    /// Convert a Montgomery-form value back to a plain residue mod n
+    fn from_mont(&self, a: &RsaBigInt) -> RsaBigInt {
+        self.redc(a)
+    }
+}
+
+/// Extract a `window_bits`-wide window from `exp`, counting windows from the
+/// least significant end (window 0 holds the lowest bits).
+fn extract_window(exp: &RsaBigInt, window_index: u32, window_bits: u32) -> usize {
+    let shift = window_index * window_bits;
+    let mask = (RsaBigInt::one() << window_bits) - 1u8;
+    ((exp >> shift) & mask).to_usize().unwrap_or(0)
+}
+
+/// Modular exponentiation for secret exponents: base^exp mod modulus.
+/// Uses a fixed 4-bit (k-ary) window with Montgomery multiplication so that
+/// the sequence of squarings and multiplies — and the per-multiply cost,
+/// since REDC replaces the division `mod_pow` does on every bit — is the
+/// same regardless of the exponent's value, unlike the binary square-and-
+/// multiply ladder in `mod_pow`, which branches on `exp.is_odd()` and leaks
+/// the exponent's Hamming weight through timing. Used by `decrypt_crt` for
+/// the private-exponent (`d_p`, `d_q`) operations; public-exponent call
+/// sites keep using the simpler, faster `mod_pow`.
+pub fn mod_pow_secret(base: &RsaBigInt, exp: &RsaBigInt, modulus: &RsaBigInt) -> RsaBigInt {
+    if modulus.is_one() {
+        return RsaBigInt::zero();
+    }
+    if exp.is_zero() {
+        return RsaBigInt::one() % modulus;
+    }
+
+    const WINDOW_BITS: u32 = 4;
+    const WINDOW_SIZE: usize = 1 << WINDOW_BITS;
+
+    let mont = MontgomeryContext::new(modulus);
+    let base_mont = mont.to_mont(&(base % modulus));
+
+    // Precompute base^0..base^(WINDOW_SIZE-1) in Montgomery form.
+    let mut table = Vec::with_capacity(WINDOW_SIZE);
+    table.push(mont.one_mont.clone());
+    for i in 1..WINDOW_SIZE {
+        let prev = table[i - 1].clone();
+        table.push(mont.mul(&prev, &base_mont));
+    }
+
+    let exp_bits = exp.bits().max(1) as u32;
+    let num_windows = (exp_bits + WINDOW_BITS - 1) / WINDOW_BITS;
+
+    // Left-to-right k-ary exponentiation: every window performs exactly
+    // WINDOW_BITS squarings followed by one table-lookup multiply, whether
+    // or not the window's value is zero.
+    let mut acc = mont.one_mont.clone();
+    for w in (0..num_windows).rev() {
+        for _ in 0..WINDOW_BITS {
+            acc = mont.mul(&acc, &acc);
+        }
+        let window_value = extract_window(exp, w, WINDOW_BITS);
+        acc = mont.mul(&acc, &table[window_value]);
+    }
+
+    mont.from_mont(&acc)
+}
+
 /// Extended Euclidean Algorithm
 /// Returns (gcd, x, y) such that a*x + b*y = gcd = gcd(a, b)
 pub fn extended_gcd(a: &RsaBigInt, b: &RsaBigInt) -> (RsaBigInt, RsaBigInt, RsaBigInt) {
@@ -145,30 +267,127 @@ pub fn random_biguint(bound: &RsaBigInt) -> RsaBigInt {
     rng.gen_biguint_below(bound)
 }
 
-/// Generate a random prime of specified bit length
+/// Primes below this bound pre-filter `random_prime` candidates by trial
+/// division, before paying for the much more expensive Miller-Rabin test.
+const SMALL_PRIME_LIMIT: u32 = 2000;
+
+/// Sieve of Eratosthenes: all primes up to and including `limit`.
+fn sieve_small_primes(limit: u32) -> Vec<u32> {
+    let mut is_composite = vec![false; (limit + 1) as usize];
+    let mut primes = Vec::new();
+
+    for candidate in 2..=limit {
+        if !is_composite[candidate as usize] {
+            primes.push(candidate);
+            let mut multiple = candidate * candidate;
+            while multiple <= limit {
+                is_composite[multiple as usize] = true;
+                multiple += candidate;
+            }
+        }
+    }
+
+    primes
+}
+
+/// Generate a random prime of specified bit length, testing primality with
+/// the default 10 Miller-Rabin rounds. See `random_prime_with_rounds` to
+/// choose the round count explicitly (e.g. via `mr_rounds_for_bit_length`).
 pub fn random_prime(bit_length: u32) -> RsaBigInt {
+    random_prime_with_rounds(bit_length, 10)
+}
+
+/// Number of Miller-Rabin rounds giving a false-positive probability of at
+/// most 2^-100 for a candidate of the given bit length, per the FIPS 186-4
+/// Appendix C.3 table. Smaller candidates need more rounds for the same
+/// confidence; this scales down as `bit_length` grows.
+pub fn mr_rounds_for_bit_length(bit_length: u32) -> u32 {
+    match bit_length {
+        0..=512 => 40,
+        513..=1024 => 30,
+        1025..=2048 => 15,
+        _ => 8,
+    }
+}
+
+/// Generate a random prime of specified bit length, testing primality with
+/// exactly `rounds` Miller-Rabin iterations.
+pub fn random_prime_with_rounds(bit_length: u32, rounds: u32) -> RsaBigInt {
     let mut rng = thread_rng();
-    let mut prime;
+    let small_primes: Vec<RsaBigInt> = sieve_small_primes(SMALL_PRIME_LIMIT)
+        .into_iter()
+        .map(RsaBigInt::from)
+        .collect();
 
     loop {
         // Generate random number with specified bit length
         let lower = RsaBigInt::from(1u8) << (bit_length - 1);
         let upper = (RsaBigInt::from(1u8) << bit_length) - 1u8;
 
-        prime = rng.gen_biguint_range(&lower, &upper);
+        let mut candidate = rng.gen_biguint_range(&lower, &upper);
 
         // Make it odd
-        if prime.is_even() {
-            prime += 1u8;
+        if candidate.is_even() {
+            candidate += 1u8;
         }
 
-        // Check primality
-        if is_probable_prime(&prime, 10) {
-            break;
+        // Track candidate mod each small prime so stepping by 2 only costs
+        // an add-and-reduce instead of a fresh division per trial.
+        let mut residues: Vec<RsaBigInt> = small_primes.iter().map(|p| &candidate % p).collect();
+
+        while candidate <= upper {
+            let divisible_by_small_prime = small_primes
+                .iter()
+                .zip(residues.iter())
+                .any(|(p, r)| r.is_zero() && candidate != *p);
+
+            if !divisible_by_small_prime && is_probable_prime(&candidate, rounds) {
+                return candidate;
+            }
+
+            candidate += 2u8;
+            for (p, r) in small_primes.iter().zip(residues.iter_mut()) {
+                let next = &*r + 2u8;
+                *r = if next >= *p { &next - p } else { next };
+            }
         }
+        // Window exhausted without finding a prime; draw a fresh candidate.
     }
+}
 
-    prime
+/// Returns true if both of `n`'s two most-significant bits (within its
+/// `bit_length`-bit range) are set, i.e. `n >= 1.1... * 2^(bit_length - 1)`.
+/// FIPS 186-4 B.3.3 requires this of each RSA prime factor so that their
+/// product `n = p*q` reliably has the full intended modulus size.
+fn has_top_two_bits_set(n: &RsaBigInt, bit_length: u32) -> bool {
+    let top_bit = &RsaBigInt::from(1u8) << (bit_length - 1);
+    let second_bit = &RsaBigInt::from(1u8) << (bit_length - 2);
+    (n & &top_bit) == top_bit && (n & &second_bit) == second_bit
+}
+
+/// Generate a random prime of the given bit length suitable for FIPS 186-4
+/// RSA key generation: in addition to passing `is_probable_prime`, its top
+/// two bits are both set. Retries until a candidate satisfies this.
+pub fn random_fips_prime(bit_length: u32, rounds: u32) -> RsaBigInt {
+    loop {
+        let candidate = random_prime_with_rounds(bit_length, rounds);
+        if has_top_two_bits_set(&candidate, bit_length) {
+            return candidate;
+        }
+    }
+}
+
+/// Generate a random safe prime `p` of the given bit length, i.e. one where
+/// `(p-1)/2` is also prime. Safe primes give the multiplicative group mod
+/// `p` a large prime-order subgroup, which some protocols require.
+pub fn random_safe_prime(bits: u32) -> RsaBigInt {
+    loop {
+        let q = random_prime(bits - 1);
+        let p = &q + &q + 1u8;
+        if is_probable_prime(&p, 10) {
+            return p;
+        }
+    }
 }
 
 /// Greatest common divisor
@@ -210,6 +429,49 @@ mod tests {
         assert_eq!((a * inv) % m, from_u64(1));
     }
 
+    #[test]
+    fn test_mod_pow_secret_matches_mod_pow() {
+        // 3^5 mod 7 = 5, same as the naive ladder.
+        let base = from_u64(3);
+        let exp = from_u64(5);
+        let modulus = from_u64(7);
+        assert_eq!(mod_pow_secret(&base, &exp, &modulus), from_u64(5));
+    }
+
+    #[test]
+    fn test_mod_pow_secret_large_values() {
+        let base = RsaBigInt::from(123456789u64);
+        let exp = RsaBigInt::from(987654321u64);
+        let modulus = RsaBigInt::from(1_000_000_007u64); // prime, odd
+
+        assert_eq!(
+            mod_pow_secret(&base, &exp, &modulus),
+            mod_pow(&base, &exp, &modulus)
+        );
+    }
+
+    #[test]
+    fn test_mod_pow_secret_zero_exponent() {
+        let base = from_u64(42);
+        let modulus = from_u64(97);
+        assert_eq!(mod_pow_secret(&base, &from_u64(0), &modulus), from_u64(1));
+    }
+
+    #[test]
+    fn test_random_prime_is_prime() {
+        let p = random_prime(64);
+        assert!(is_probable_prime(&p, 20));
+        assert!(p.bits() <= 64 && p.bits() >= 63);
+    }
+
+    #[test]
+    fn test_random_safe_prime() {
+        let p = random_safe_prime(64);
+        assert!(is_probable_prime(&p, 20));
+        let q = (&p - 1u8) / 2u8;
+        assert!(is_probable_prime(&q, 20));
+    }
+
     #[test]
     fn test_is_probable_prime() {
         // 2 is prime
@@ -1,9 +1,9 @@
 // RSA Decryption Implementation
 // Implements RSA decryption with Chinese Remainder Theorem (CRT) optimization
 
-use super::bigint::{RsaBigInt, from_bytes, mod_pow};
+use super::bigint::{RsaBigInt, from_bytes, from_u64, gcd, mod_inverse, mod_pow, mod_pow_secret, random_biguint};
 use super::keygen::RsaPrivateKey;
-use super::padding::{unpad_pkcs1_v15, PaddedData};
+use super::padding::{unpad_pkcs1_v15, unpad_oaep, OaepHash, PaddedData};
 
 /// Decrypt ciphertext bytes using RSA private key
 /// Returns plaintext as bytes
@@ -22,14 +22,18 @@ pub fn decrypt_bytes(ciphertext: &[u8], private_key: &RsaPrivateKey) -> Result<V
     }
 
     // Use CRT-based decryption for better performance
-    let m = decrypt_crt(&c, private_key);
+    let m = decrypt_crt(&c, private_key)?;
 
-    // Convert to bytes
+    // Convert to bytes, left-padded to the key size: `to_bytes_be` strips
+    // the EM's leading 0x00, but `unpad_pkcs1_v15` requires it to still be
+    // there to recognize the "00 02" header.
     let m_bytes = m.to_bytes_be();
+    let mut em = vec![0u8; key_bytes];
+    let start = key_bytes.saturating_sub(m_bytes.len());
+    em[start..].copy_from_slice(&m_bytes);
 
-    // Remove leading zeros
     let padded = PaddedData {
-        data: m_bytes,
+        data: em,
         expected_size: key_bytes,
     };
 
@@ -39,14 +43,42 @@ pub fn decrypt_bytes(ciphertext: &[u8], private_key: &RsaPrivateKey) -> Result<V
     Ok(plaintext)
 }
 
-/// Decrypt using Chinese Remainder Theorem (CRT)
-/// This is faster than regular decryption because we work with smaller numbers
-fn decrypt_crt(c: &RsaBigInt, key: &RsaPrivateKey) -> RsaBigInt {
-    // m1 = c^d_p mod p
-    let m1 = mod_pow(c, &key.d_p, &key.p);
+/// Decrypt using Chinese Remainder Theorem (CRT), with RSA base blinding and
+/// a fault-detection check.
+/// This is faster than regular decryption because we work with smaller numbers.
+/// Also used by `sign` to apply the private exponent for signing, since
+/// "sign" is the same modular operation as "decrypt" in textbook RSA.
+///
+/// Base blinding: a fresh random `r`, coprime to `n`, turns the input into
+/// `c' = c * r^e mod n` before the CRT exponentiation runs; the result is
+/// unblinded with `r^-1 mod n` afterwards. Since `r` is secret and changes
+/// every call, this decorrelates the CRT halves' timing from the input,
+/// defeating timing attacks built around a chosen ciphertext. The unblinded
+/// result is then checked by recomputing `m^e mod n` and comparing against
+/// `c`, which catches the classic Bellcore CRT fault attack (a single
+/// corrupted CRT half otherwise leaks a prime factor via `gcd(c - m^e, n)`).
+pub(super) fn decrypt_crt(c: &RsaBigInt, key: &RsaPrivateKey) -> Result<RsaBigInt, String> {
+    let n = &key.n;
+    let one = from_u64(1);
+
+    // Pick r coprime to n for this call.
+    let r = loop {
+        let candidate = random_biguint(n);
+        if candidate > one && gcd(&candidate, n) == one {
+            break candidate;
+        }
+    };
+    let r_inv = mod_inverse(&r, n).ok_or("Failed to compute blinding factor inverse")?;
+
+    // c' = c * r^e mod n
+    let r_pow_e = mod_pow(&r, &key.e, n);
+    let blinded_c = (c * &r_pow_e) % n;
 
-    // m2 = c^d_q mod q
-    let m2 = mod_pow(c, &key.d_q, &key.q);
+    // m1 = c'^d_p mod p (private exponent: use the constant-time ladder)
+    let m1 = mod_pow_secret(&blinded_c, &key.d_p, &key.p);
+
+    // m2 = c'^d_q mod q (private exponent: use the constant-time ladder)
+    let m2 = mod_pow_secret(&blinded_c, &key.d_q, &key.q);
 
     // h = (m1 - m2) * q_inv mod p
     let m1_cloned = m1.clone();
@@ -58,16 +90,19 @@ fn decrypt_crt(c: &RsaBigInt, key: &RsaPrivateKey) -> RsaBigInt {
     };
     h = (h * &key.q_inv) % &key.p;
 
-    // m = m2 + q * h
-    let m = m2 + &key.q * h;
+    // blinded_m = m2 + q * h
+    let blinded_m = m2 + &key.q * h;
+    let blinded_m = if blinded_m >= *n { blinded_m - n } else { blinded_m };
 
-    // Ensure m < n
-    let n = &key.n;
-    if m >= *n {
-        return m - n;
+    // Unblind: m = blinded_m * r^-1 mod n
+    let m = (blinded_m * &r_inv) % n;
+
+    // Fault check: re-encrypting a correctly computed m must reproduce c.
+    if mod_pow(&m, &key.e, n) != *c {
+        return Err("CRT decryption fault detected".to_string());
     }
 
-    m
+    Ok(m)
 }
 
 /// Decrypt ciphertext to a string
@@ -87,11 +122,35 @@ pub fn decrypt_to_u64(ciphertext: &[u8], private_key: &RsaPrivateKey) -> Result<
     Ok(u64::from_le_bytes(bytes))
 }
 
-/// Decrypt data with OAEP padding (placeholder for future implementation)
-pub fn decrypt_oaep(ciphertext: &[u8], private_key: &RsaPrivateKey, _label: &[u8]) -> Result<Vec<u8>, String> {
-    // TODO: Implement OAEP unpadding
-    // For now, fall back to PKCS#1 v1.5
-    decrypt_bytes(ciphertext, private_key)
+/// Decrypt ciphertext produced by `encrypt_oaep`, reversing RSA-OAEP
+/// (PKCS#1 v2) padding with the given hash and label.
+pub fn decrypt_oaep(ciphertext: &[u8], private_key: &RsaPrivateKey, label: &[u8], hash: OaepHash) -> Result<Vec<u8>, String> {
+    // Convert ciphertext to big integer
+    let c = from_bytes(ciphertext);
+
+    // Validate ciphertext size
+    let key_bytes: usize = ((private_key.bit_length() + 7) / 8) as usize;
+    if ciphertext.len() != key_bytes {
+        return Err(format!(
+            "Invalid ciphertext length: expected {} bytes, got {}",
+            key_bytes,
+            ciphertext.len()
+        ));
+    }
+
+    // Use CRT-based decryption for better performance
+    let m = decrypt_crt(&c, private_key)?;
+
+    // Convert to bytes, padded to the key size
+    let m_bytes = m.to_bytes_be();
+    let mut em = vec![0u8; key_bytes];
+    let start = key_bytes.saturating_sub(m_bytes.len());
+    em[start..].copy_from_slice(&m_bytes);
+
+    let padded = PaddedData { data: em, expected_size: key_bytes };
+
+    // Remove OAEP padding
+    unpad_oaep(padded, label, hash)
 }
 
 #[cfg(test)]
@@ -175,4 +234,31 @@ mod tests {
             test_roundtrip(&keypair, &message);
         }
     }
+
+    #[test]
+    fn test_decrypt_oaep_wrong_label() {
+        use super::super::encrypt::encrypt_oaep;
+        use super::super::padding::OaepHash;
+
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let message = b"Secret";
+
+        let ciphertext = encrypt_oaep(message, &keypair.public_key, b"context-a", OaepHash::Sha256).unwrap();
+        let result = decrypt_oaep(&ciphertext, &keypair.private_key, b"context-b", OaepHash::Sha256);
+        assert!(result.is_err()); // lHash mismatch must be rejected
+    }
+
+    #[test]
+    fn test_decrypt_crt_blinding_roundtrip() {
+        // Blinding uses a fresh random factor each call, so repeated
+        // decryptions of the same ciphertext must still agree.
+        let keypair = generate_keypair(512, 65537).unwrap();
+        let message = b"Blind me twice";
+        let ciphertext = keypair.public_key.encrypt(message).unwrap();
+
+        let first = decrypt_bytes(&ciphertext, &keypair.private_key).unwrap();
+        let second = decrypt_bytes(&ciphertext, &keypair.private_key).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(message.as_slice(), first.as_slice());
+    }
 }
\ No newline at end of file
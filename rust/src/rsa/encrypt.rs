@@ -3,7 +3,7 @@
 
 use super::bigint::{RsaBigInt, from_bytes, to_bytes, mod_pow};
 use super::keygen::RsaPublicKey;
-use super::padding::{pad_pkcs1_v15, PaddedData};
+use super::padding::{pad_pkcs1_v15, pad_oaep, OaepHash, PaddedData};
 
 /// Encrypt bytes using RSA public key
 /// Returns ciphertext as bytes
@@ -40,11 +40,26 @@ pub fn encrypt_u64(value: u64, public_key: &RsaPublicKey) -> Result<Vec<u8>, Str
     encrypt_bytes(&bytes, public_key)
 }
 
-/// Encrypt data with OAEP padding (placeholder for future implementation)
-pub fn encrypt_oaep(plaintext: &[u8], public_key: &RsaPublicKey, _label: &[u8]) -> Result<Vec<u8>, String> {
-    // TODO: Implement OAEP padding
-    // For now, fall back to PKCS#1 v1.5
-    encrypt_bytes(plaintext, public_key)
+/// Encrypt bytes using RSA-OAEP (PKCS#1 v2) padding with the given hash and
+/// label.
+pub fn encrypt_oaep(plaintext: &[u8], public_key: &RsaPublicKey, label: &[u8], hash: OaepHash) -> Result<Vec<u8>, String> {
+    // Apply OAEP padding
+    let padded = pad_oaep(plaintext, public_key, label, hash)?;
+
+    // Convert to big integer
+    let m = from_bytes(&padded.data);
+
+    // Compute c = m^e mod n
+    let c = mod_pow(&m, &public_key.e, &public_key.n);
+
+    // Convert to bytes, padded to the key size
+    let ciphertext = to_bytes(&c);
+    let key_bytes = padded.expected_size;
+    let mut result = vec![0u8; key_bytes];
+    let start = key_bytes.saturating_sub(ciphertext.len());
+    result[start..].copy_from_slice(&ciphertext);
+
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -100,4 +115,26 @@ mod tests {
         let result = encrypt_bytes(message, &keypair.public_key);
         assert!(result.is_err()); // Empty message should fail padding
     }
+
+    #[test]
+    fn test_encrypt_oaep_roundtrip() {
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let message = b"Hello, OAEP!";
+
+        let ciphertext = encrypt_oaep(message, &keypair.public_key, b"", OaepHash::Sha256).unwrap();
+        assert_eq!(ciphertext.len(), 256); // 2048 bits = 256 bytes
+
+        let decrypted = super::super::decrypt::decrypt_oaep(&ciphertext, &keypair.private_key, b"", OaepHash::Sha256).unwrap();
+        assert_eq!(message.as_slice(), decrypted.as_slice());
+    }
+
+    #[test]
+    fn test_encrypt_oaep_nondeterministic() {
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let message = b"Same message twice";
+
+        let c1 = encrypt_oaep(message, &keypair.public_key, b"", OaepHash::Sha256).unwrap();
+        let c2 = encrypt_oaep(message, &keypair.public_key, b"", OaepHash::Sha256).unwrap();
+        assert_ne!(c1, c2); // Random seed must vary each call
+    }
 }
@@ -3,8 +3,10 @@
 
 use super::bigint::{
     RsaBigInt, from_u64, mod_inverse, is_probable_prime, random_prime,
+    random_fips_prime, mr_rounds_for_bit_length,
     gcd, lcm,
 };
+use super::padding::{EncryptionPadding, OaepHash};
 
 /// RSA Public Key
 #[derive(Debug, Clone, PartialEq)]
@@ -17,6 +19,7 @@ pub struct RsaPublicKey {
 #[derive(Debug, Clone, PartialEq)]
 pub struct RsaPrivateKey {
     pub n: RsaBigInt,      // Modulus (same as public)
+    pub e: RsaBigInt,      // Public exponent, kept alongside d for CRT blinding
     pub d: RsaBigInt,      // Private exponent
     pub p: RsaBigInt,      // First prime factor
     pub q: RsaBigInt,      // Second prime factor
@@ -41,11 +44,41 @@ impl RsaPublicKey {
         (n_bytes.len() * 8) as u32
     }
 
-    /// Encrypt a message using this public key
+    /// Encrypt a message using this public key with PKCS#1 v1.5 padding
     /// Returns ciphertext as bytes
     pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, String> {
-        use super::encrypt::encrypt_bytes;
-        encrypt_bytes(plaintext, self)
+        self.encrypt_with_padding(plaintext, EncryptionPadding::Pkcs1V15)
+    }
+
+    /// Encrypt a message using this public key with the selected padding
+    /// scheme. OAEP is applied with an empty label.
+    pub fn encrypt_with_padding(&self, plaintext: &[u8], padding: EncryptionPadding) -> Result<Vec<u8>, String> {
+        match padding {
+            EncryptionPadding::Pkcs1V15 => {
+                use super::encrypt::encrypt_bytes;
+                encrypt_bytes(plaintext, self)
+            }
+            EncryptionPadding::Oaep(hash) => {
+                use super::encrypt::encrypt_oaep;
+                encrypt_oaep(plaintext, self, b"", hash)
+            }
+        }
+    }
+
+    /// Verify an RSASSA-PSS signature (SHA-256, salt length = hash output)
+    /// produced by the matching `RsaPrivateKey::sign`.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool, String> {
+        use super::sign::verify_pss;
+        verify_pss(message, signature, self, OaepHash::Sha256, OaepHash::Sha256.output_len())
+    }
+
+    /// Construct a public key from raw components, e.g. when importing one
+    /// loaded from PEM/DER via `rsa::format`.
+    pub fn from_components(n: RsaBigInt, e: RsaBigInt) -> Result<RsaPublicKey, String> {
+        if n == from_u64(0) || e == from_u64(0) {
+            return Err("Modulus and exponent must be non-zero".to_string());
+        }
+        Ok(RsaPublicKey { n, e })
     }
 }
 
@@ -56,11 +89,111 @@ impl RsaPrivateKey {
         (n_bytes.len() * 8) as u32
     }
 
-    /// Decrypt a ciphertext using this private key
+    /// Decrypt a ciphertext using this private key with PKCS#1 v1.5 padding
     /// Returns plaintext as bytes
     pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, String> {
-        use super::decrypt::decrypt_bytes;
-        decrypt_bytes(ciphertext, self)
+        self.decrypt_with_padding(ciphertext, EncryptionPadding::Pkcs1V15)
+    }
+
+    /// Decrypt a ciphertext using this private key with the selected
+    /// padding scheme. OAEP is applied with an empty label.
+    pub fn decrypt_with_padding(&self, ciphertext: &[u8], padding: EncryptionPadding) -> Result<Vec<u8>, String> {
+        match padding {
+            EncryptionPadding::Pkcs1V15 => {
+                use super::decrypt::decrypt_bytes;
+                decrypt_bytes(ciphertext, self)
+            }
+            EncryptionPadding::Oaep(hash) => {
+                use super::decrypt::decrypt_oaep;
+                decrypt_oaep(ciphertext, self, b"", hash)
+            }
+        }
+    }
+
+    /// Sign a message with RSASSA-PSS (SHA-256, salt length = hash output).
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>, String> {
+        use super::sign::sign_pss;
+        sign_pss(message, self, OaepHash::Sha256, OaepHash::Sha256.output_len())
+    }
+
+    /// Construct a private key from raw components — e.g. a key loaded
+    /// from PEM/DER via `rsa::format`, or one assembled by a caller who
+    /// obtained the components from another RSA implementation. `d_p`,
+    /// `d_q`, and `q_inv` are recomputed from `d`, `p`, and `q` when not
+    /// supplied; if supplied, they're checked rather than trusted.
+    ///
+    /// Validates full internal consistency before returning `Ok`: that
+    /// `n == p * q`, that `p` and `q` are probable primes, that
+    /// `e * d ≡ 1 (mod lcm(p-1, q-1))`, and that the CRT parameters satisfy
+    /// `d_p ≡ d (mod p-1)`, `d_q ≡ d (mod q-1)`, and `q * q_inv ≡ 1 (mod p)`
+    /// — returning a descriptive error on the first mismatch found. This
+    /// lets tampered or corrupt key material be rejected before it ever
+    /// reaches `decrypt`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_components(
+        n: RsaBigInt,
+        e: RsaBigInt,
+        d: RsaBigInt,
+        p: RsaBigInt,
+        q: RsaBigInt,
+        d_p: Option<RsaBigInt>,
+        d_q: Option<RsaBigInt>,
+        q_inv: Option<RsaBigInt>,
+    ) -> Result<RsaPrivateKey, String> {
+        if &p * &q != n {
+            return Err("Inconsistent key: n != p * q".to_string());
+        }
+        if !is_probable_prime(&p, 20) {
+            return Err("Inconsistent key: p is not a probable prime".to_string());
+        }
+        if !is_probable_prime(&q, 20) {
+            return Err("Inconsistent key: q is not a probable prime".to_string());
+        }
+
+        let p_minus_1 = &p - 1u8;
+        let q_minus_1 = &q - 1u8;
+        let lambda = lcm(&p_minus_1, &q_minus_1);
+        if (&e * &d) % &lambda != from_u64(1) {
+            return Err("Inconsistent key: e * d != 1 mod lcm(p-1, q-1)".to_string());
+        }
+
+        let expected_d_p = &d % &p_minus_1;
+        let d_p = match d_p {
+            Some(given) if given == expected_d_p => given,
+            Some(_) => return Err("Inconsistent key: d_p != d mod (p-1)".to_string()),
+            None => expected_d_p,
+        };
+
+        let expected_d_q = &d % &q_minus_1;
+        let d_q = match d_q {
+            Some(given) if given == expected_d_q => given,
+            Some(_) => return Err("Inconsistent key: d_q != d mod (q-1)".to_string()),
+            None => expected_d_q,
+        };
+
+        let q_inv = match q_inv {
+            Some(given) => {
+                if (&q * &given) % &p != from_u64(1) {
+                    return Err("Inconsistent key: q * q_inv != 1 mod p".to_string());
+                }
+                given
+            }
+            None => match mod_inverse(&q, &p) {
+                Some(inv) => inv,
+                None => return Err("Failed to compute q^(-1) mod p".to_string()),
+            },
+        };
+
+        Ok(RsaPrivateKey {
+            n,
+            e,
+            d,
+            p,
+            q,
+            d_p,
+            d_q,
+            q_inv,
+        })
     }
 }
 
@@ -105,6 +238,14 @@ pub fn generate_keypair(bit_length: u32, e: u64) -> Result<RsaKeyPair, String> {
         (p, q)
     };
 
+    assemble_keypair(bit_length, e, p, q)
+}
+
+/// Shared key assembly: given a bit length, public exponent, and two primes
+/// (already ordered p > q), compute φ(n), d, and the CRT parameters and
+/// build the resulting key pair. Used by `generate_keypair`,
+/// `generate_keypair_strict` and `generate_keypair_fips`.
+fn assemble_keypair(bit_length: u32, e: RsaBigInt, p: RsaBigInt, q: RsaBigInt) -> Result<RsaKeyPair, String> {
     // Step 2: Compute n = p * q
     let n = &p * &q;
 
@@ -144,6 +285,7 @@ pub fn generate_keypair(bit_length: u32, e: u64) -> Result<RsaKeyPair, String> {
 
     let private_key = RsaPrivateKey {
         n: n.clone(),
+        e: e.clone(),
         d: d.clone(),
         p,
         q,
@@ -164,6 +306,112 @@ pub fn generate_default_keypair() -> Result<RsaKeyPair, String> {
     generate_keypair(2048, 65537)
 }
 
+/// Generate RSA key pair like `generate_keypair`, but also require that
+/// `p-1` and `q-1` are each individually coprime with `e` (not just their
+/// product φ(n)), retrying with fresh primes instead of failing outright
+/// when a draw doesn't satisfy it. Useful for callers that want a
+/// guaranteed-usable key without surfacing a transient coprimality failure.
+pub fn generate_keypair_strict(bit_length: u32, e: u64) -> Result<RsaKeyPair, String> {
+    if bit_length < 512 {
+        return Err("Bit length must be at least 512".to_string());
+    }
+    if bit_length % 2 != 0 {
+        return Err("Bit length must be even (p and q should have equal bit length)".to_string());
+    }
+
+    const MAX_ATTEMPTS: u32 = 100;
+    let e_big = from_u64(e);
+    let half_bits = bit_length / 2;
+
+    for _ in 0..MAX_ATTEMPTS {
+        let p = random_prime(half_bits);
+        let q = random_prime(half_bits);
+        if p == q {
+            continue;
+        }
+        let (p, q) = if p < q { (q, p) } else { (p, q) };
+
+        if gcd(&e_big, &(&p - 1u8)) != from_u64(1) || gcd(&e_big, &(&q - 1u8)) != from_u64(1) {
+            continue;
+        }
+
+        return assemble_keypair(bit_length, e_big, p, q);
+    }
+
+    Err(format!(
+        "Could not find primes with p-1/q-1 coprime to e={} after {} attempts",
+        e, MAX_ATTEMPTS
+    ))
+}
+
+/// Generate an RSA key pair meeting the FIPS 186-4 B.3.3 prime-generation
+/// constraints:
+/// - each prime's top two bits are set, so `n = p*q` reliably has the full
+///   `bit_length` (see `random_fips_prime`);
+/// - `|p - q| > 2^(half_bits - 100)`, i.e. the primes differ in their top
+///   ~100 bits, to defeat Fermat factoring;
+/// - `gcd(e, p-1) = gcd(e, q-1) = 1` is checked directly, not just
+///   `gcd(e, φ(n))`;
+/// - the number of Miller-Rabin rounds is scaled to `bit_length` via
+///   `mr_rounds_for_bit_length` instead of a fixed count.
+///
+/// Set `safe_primes` to additionally require `(p-1)/2` and `(q-1)/2` to
+/// also be probable primes ("strong primes"). All of this is implemented
+/// as retry loops, rather than `generate_keypair`'s recursive re-draw on
+/// `p == q`, so a long run of bad luck can't grow the call stack.
+pub fn generate_keypair_fips(bit_length: u32, e: u64, safe_primes: bool) -> Result<RsaKeyPair, String> {
+    if bit_length < 512 {
+        return Err("Bit length must be at least 512".to_string());
+    }
+    if bit_length % 2 != 0 {
+        return Err("Bit length must be even (p and q should have equal bit length)".to_string());
+    }
+
+    const MAX_ATTEMPTS: u32 = 200;
+    let e_big = from_u64(e);
+    let half_bits = bit_length / 2;
+    let rounds = mr_rounds_for_bit_length(bit_length);
+    let min_distance = RsaBigInt::from(1u8) << half_bits.saturating_sub(100).max(1);
+
+    for _ in 0..MAX_ATTEMPTS {
+        let p = fips_candidate_prime(half_bits, rounds, safe_primes);
+        let q = fips_candidate_prime(half_bits, rounds, safe_primes);
+
+        if p == q {
+            continue;
+        }
+        let (p, q) = if p < q { (q, p) } else { (p, q) };
+
+        if &p - &q <= min_distance {
+            continue;
+        }
+
+        let p_minus_1 = &p - 1u8;
+        let q_minus_1 = &q - 1u8;
+        if gcd(&e_big, &p_minus_1) != from_u64(1) || gcd(&e_big, &q_minus_1) != from_u64(1) {
+            continue;
+        }
+
+        return assemble_keypair(bit_length, e_big, p, q);
+    }
+
+    Err(format!(
+        "Could not find FIPS 186-4 compliant primes for bit_length={} after {} attempts",
+        bit_length, MAX_ATTEMPTS
+    ))
+}
+
+/// Draw one FIPS-shaped prime candidate, optionally requiring it be a
+/// "strong"/safe prime (`(candidate - 1) / 2` also prime).
+fn fips_candidate_prime(bits: u32, rounds: u32, safe_primes: bool) -> RsaBigInt {
+    loop {
+        let candidate = random_fips_prime(bits, rounds);
+        if !safe_primes || is_probable_prime(&((&candidate - 1u8) / 2u8), rounds) {
+            return candidate;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,6 +438,154 @@ mod tests {
         assert_eq!(message.as_slice(), decrypted.as_slice());
     }
 
+    #[test]
+    fn test_generate_keypair_strict() {
+        let keypair = generate_keypair_strict(512, 65537).unwrap();
+        assert_eq!(keypair.bit_length(), 512);
+
+        // e must be coprime with p-1 and q-1 individually, not just φ(n).
+        let e = &keypair.public_key.e;
+        let p_minus_1 = &keypair.private_key.p - 1u8;
+        let q_minus_1 = &keypair.private_key.q - 1u8;
+        assert_eq!(gcd(e, &p_minus_1), from_u64(1));
+        assert_eq!(gcd(e, &q_minus_1), from_u64(1));
+
+        let message = b"Strict keygen roundtrip";
+        let ciphertext = keypair.public_key.encrypt(message).unwrap();
+        let decrypted = keypair.private_key.decrypt(&ciphertext).unwrap();
+        assert_eq!(message.as_slice(), decrypted.as_slice());
+    }
+
+    #[test]
+    fn test_generate_keypair_fips() {
+        let keypair = generate_keypair_fips(512, 65537, false).unwrap();
+        assert_eq!(keypair.bit_length(), 512);
+
+        let half_bits = 256u32;
+        let min_distance = RsaBigInt::from(1u8) << half_bits.saturating_sub(100).max(1);
+        assert!(&keypair.private_key.p - &keypair.private_key.q > min_distance);
+
+        let message = b"FIPS keygen roundtrip";
+        let ciphertext = keypair.public_key.encrypt(message).unwrap();
+        let decrypted = keypair.private_key.decrypt(&ciphertext).unwrap();
+        assert_eq!(message.as_slice(), decrypted.as_slice());
+    }
+
+    #[test]
+    fn test_generate_keypair_fips_safe_primes() {
+        let keypair = generate_keypair_fips(512, 65537, true).unwrap();
+        let p_sophie = (&keypair.private_key.p - 1u8) / 2u8;
+        let q_sophie = (&keypair.private_key.q - 1u8) / 2u8;
+        assert!(is_probable_prime(&p_sophie, 20));
+        assert!(is_probable_prime(&q_sophie, 20));
+    }
+
+    #[test]
+    fn test_private_key_from_components_roundtrip() {
+        let keypair = generate_keypair(512, 65537).unwrap();
+        let original = keypair.private_key.clone();
+
+        // Omitting the CRT parameters recomputes them.
+        let rebuilt = RsaPrivateKey::from_components(
+            original.n.clone(),
+            original.e.clone(),
+            original.d.clone(),
+            original.p.clone(),
+            original.q.clone(),
+            None,
+            None,
+            None,
+        ).unwrap();
+        assert_eq!(rebuilt, original);
+
+        // Supplying matching CRT parameters also succeeds.
+        let rebuilt_with_crt = RsaPrivateKey::from_components(
+            original.n.clone(),
+            original.e.clone(),
+            original.d.clone(),
+            original.p.clone(),
+            original.q.clone(),
+            Some(original.d_p.clone()),
+            Some(original.d_q.clone()),
+            Some(original.q_inv.clone()),
+        ).unwrap();
+        assert_eq!(rebuilt_with_crt, original);
+    }
+
+    #[test]
+    fn test_private_key_from_components_rejects_tampered_crt_param() {
+        let keypair = generate_keypair(512, 65537).unwrap();
+        let k = &keypair.private_key;
+
+        let tampered_d_p = &k.d_p + 1u8;
+        let result = RsaPrivateKey::from_components(
+            k.n.clone(),
+            k.e.clone(),
+            k.d.clone(),
+            k.p.clone(),
+            k.q.clone(),
+            Some(tampered_d_p),
+            None,
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_private_key_from_components_rejects_bad_modulus() {
+        let keypair = generate_keypair(512, 65537).unwrap();
+        let k = &keypair.private_key;
+
+        let result = RsaPrivateKey::from_components(
+            &k.n + 1u8,
+            k.e.clone(),
+            k.d.clone(),
+            k.p.clone(),
+            k.q.clone(),
+            None,
+            None,
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_public_key_from_components() {
+        let keypair = generate_keypair(512, 65537).unwrap();
+        let rebuilt = RsaPublicKey::from_components(
+            keypair.public_key.n.clone(),
+            keypair.public_key.e.clone(),
+        ).unwrap();
+        assert_eq!(rebuilt, keypair.public_key);
+
+        assert!(RsaPublicKey::from_components(from_u64(0), from_u64(65537)).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_with_padding_oaep() {
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let message = b"Select OAEP via the enum";
+
+        let ciphertext = keypair.public_key
+            .encrypt_with_padding(message, EncryptionPadding::Oaep(OaepHash::Sha256))
+            .unwrap();
+        let decrypted = keypair.private_key
+            .decrypt_with_padding(&ciphertext, EncryptionPadding::Oaep(OaepHash::Sha256))
+            .unwrap();
+
+        assert_eq!(message.as_slice(), decrypted.as_slice());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let message = b"Sign via the key method";
+
+        let signature = keypair.private_key.sign(message).unwrap();
+        assert!(keypair.public_key.verify(message, &signature).unwrap());
+        assert!(!keypair.public_key.verify(b"tampered", &signature).unwrap());
+    }
+
     #[test]
     fn test_key_properties() {
         let keypair = generate_keypair(512, 17).unwrap();
@@ -6,8 +6,12 @@ pub mod keygen;
 pub mod encrypt;
 pub mod decrypt;
 pub mod padding;
+pub mod sign;
+pub mod format;
 
 pub use keygen::{generate_keypair, RsaKeyPair, RsaPublicKey, RsaPrivateKey};
-pub use encrypt::{encrypt_bytes, encrypt_string, encrypt_u64};
-pub use decrypt::{decrypt_bytes, decrypt_to_string, decrypt_to_u64};
-pub use padding::{pad_pkcs1_v15, unpad_pkcs1_v15, PaddedData};
\ No newline at end of file
+pub use encrypt::{encrypt_bytes, encrypt_string, encrypt_u64, encrypt_oaep};
+pub use decrypt::{decrypt_bytes, decrypt_to_string, decrypt_to_u64, decrypt_oaep};
+pub use padding::{pad_pkcs1_v15, unpad_pkcs1_v15, PaddedData, OaepHash, EncryptionPadding};
+pub use sign::{sign_pkcs1_v15, verify_pkcs1_v15, sign_pss, verify_pss};
+pub use format::KeyEncoding;
\ No newline at end of file
@@ -67,97 +67,219 @@ pub fn pad_pkcs1_v15(data: &[u8], _public_key: &super::keygen::RsaPublicKey) ->
     })
 }
 
-/// Remove PKCS#1 v1.5 padding from encrypted data
-/// Validates the padding structure and extracts the original data
+/// Remove PKCS#1 v1.5 padding from encrypted data.
+///
+/// Never early-returns on a structural failure: every byte is scanned and
+/// every check (leading `0x00 0x02`, separator position, trailing data)
+/// folds into a single `bad` flag before the one decision point at the end.
+/// This mirrors `unpad_oaep`'s approach and exists for the same reason —
+/// returning distinct errors (or taking a different amount of time) for
+/// "wrong first byte" vs. "no separator found" is a textbook Bleichenbacher
+/// padding oracle, so both the branch and the error message must be
+/// independent of *where* (or whether) the padding is malformed.
 pub fn unpad_pkcs1_v15(padded: PaddedData) -> Result<Vec<u8>, String> {
     let data = padded.data;
+    let len = data.len();
 
-    // Validate minimum length
-    if data.len() < 11 {
-        return Err("Invalid padding: data too short".to_string());
+    if len < 11 {
+        return Err("Invalid PKCS#1 v1.5 padding".to_string());
     }
 
-    // Check leading bytes
-    if data[0] != 0x00 {
-        return Err("Invalid padding: first byte must be 0x00".to_string());
+    let mut bad: u8 = (data[0] != 0x00) as u8;
+    bad |= (data[1] != 0x02) as u8;
+
+    // Walk the whole buffer once, tracking whether the 0x00 separator has
+    // been seen with an arithmetic flag rather than a `break`, so the loop
+    // always does the same amount of work regardless of where (or whether)
+    // a valid separator appears.
+    let mut looking: u8 = 1;
+    let mut sep_index = len;
+    for i in 2..len {
+        let is_zero = (data[i] == 0x00) as u8;
+        let take = looking & is_zero;
+        sep_index = take as usize * i + (1 - take as usize) * sep_index;
+        looking &= 1 - is_zero;
     }
+    bad |= looking; // Separator was never found.
 
-    if data[1] != 0x02 {
-        return Err("Invalid padding: second byte must be 0x02".to_string());
+    // The separator must be at index >= 10 (0x00 0x02 + at least 8 bytes of
+    // PS) and leave at least one byte of data after it.
+    bad |= (sep_index < 10) as u8;
+    bad |= (sep_index + 1 >= len) as u8;
+
+    if bad != 0 {
+        return Err("Invalid PKCS#1 v1.5 padding".to_string());
     }
 
-    // Find the separator byte (0x00)
-    let separator_pos = match data[2..].iter().position(|&b| b == 0x00) {
-        Some(pos) => pos + 2, // +2 because we started from index 2
-        None => {
-            return Err("Invalid padding: no separator byte found".to_string());
+    Ok(data[sep_index + 1..].to_vec())
+}
+
+/// Hash function selectable for OAEP's two internal uses (lHash and MGF1),
+/// and for PSS/PKCS#1 v1.5 signing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OaepHash {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl OaepHash {
+    /// Output length of the underlying hash, in bytes.
+    pub fn output_len(&self) -> usize {
+        match self {
+            OaepHash::Sha1 => 20,
+            OaepHash::Sha256 => 32,
+            OaepHash::Sha384 => 48,
+            OaepHash::Sha512 => 64,
         }
-    };
+    }
 
-    // The separator must be at least at position 10 (after 0x00 0x02 + 8 bytes minimum)
-    if separator_pos < 10 {
-        return Err("Invalid padding: padding too short".to_string());
+    pub(super) fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            OaepHash::Sha1 => {
+                use sha1::{Digest, Sha1};
+                Sha1::digest(data).to_vec()
+            }
+            OaepHash::Sha256 => {
+                use sha2::{Digest, Sha256};
+                Sha256::digest(data).to_vec()
+            }
+            OaepHash::Sha384 => {
+                use sha2::{Digest, Sha384};
+                Sha384::digest(data).to_vec()
+            }
+            OaepHash::Sha512 => {
+                use sha2::{Digest, Sha512};
+                Sha512::digest(data).to_vec()
+            }
+        }
     }
+}
 
-    // Extract the original data (after the separator)
-    let original_data = &data[separator_pos + 1..];
+/// Selects which padding scheme `RsaPublicKey::encrypt_with_padding` and
+/// `RsaPrivateKey::decrypt_with_padding` use. PKCS#1 v1.5 stays available
+/// for compatibility; OAEP is the modern, recommended choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionPadding {
+    Pkcs1V15,
+    Oaep(OaepHash),
+}
 
-    if original_data.is_empty() {
-        return Err("Invalid padding: no data after separator".to_string());
+/// MGF1 mask generation function (PKCS#1): H(seed || be32(0)) || H(seed ||
+/// be32(1)) || ... truncated to `mask_len` bytes. Shared with `sign`'s PSS
+/// padding, which uses the same mask generator.
+pub(super) fn mgf1(seed: &[u8], mask_len: usize, hash: OaepHash) -> Vec<u8> {
+    let mut output = Vec::with_capacity(mask_len + hash.output_len());
+    let mut counter: u32 = 0;
+    while output.len() < mask_len {
+        let mut block = Vec::with_capacity(seed.len() + 4);
+        block.extend_from_slice(seed);
+        block.extend_from_slice(&counter.to_be_bytes());
+        output.extend_from_slice(&hash.digest(&block));
+        counter += 1;
     }
-
-    Ok(original_data.to_vec())
+    output.truncate(mask_len);
+    output
 }
 
-/// PKCS#1 v1.5 Signature padding (EMSA-PKCS1-v1_5)
-/// Format: 0x00 || 0x01 || PS (0xFF) || 0x00 || DER(OID) || digest
-pub fn pad_for_signature(data: &[u8], _algorithm_oid: &[u8]) -> Result<Vec<u8>, String> {
-    // This is a simplified version for demonstration
-    // Full implementation would include proper DER-encoded OID
+/// RSA-OAEP (PKCS#1 v2) encoding using MGF1.
+/// Format: 0x00 || maskedSeed || maskedDB, where DB = lHash || PS || 0x01 || M.
+pub fn pad_oaep(data: &[u8], public_key: &super::keygen::RsaPublicKey, label: &[u8], hash: OaepHash) -> Result<PaddedData, String> {
+    let key_size: usize = ((public_key.bit_length() + 7) / 8) as usize;
+    let h_len = hash.output_len();
 
-    let digest_size = data.len();
-    let total_size = 11 + 10 + digest_size; // Basic overhead + SHA-256 OID placeholder + digest
+    if key_size < 2 * h_len + 2 {
+        return Err("Key too small for OAEP with this hash".to_string());
+    }
+    let max_data_len = key_size - 2 * h_len - 2;
+    if data.len() > max_data_len {
+        return Err(format!(
+            "Data too large: max {} bytes, got {}",
+            max_data_len,
+            data.len()
+        ));
+    }
 
-    let mut result = Vec::with_capacity(total_size);
-    result.push(0x00);
-    result.push(0x01);
+    let l_hash = hash.digest(label);
+    let ps_len = max_data_len - data.len();
 
-    // PS = 0xFF bytes
-    let ps_size = total_size - 2 - 1 - 10 - digest_size; // 00 01 + 00 + OID + digest
-    result.extend(vec![0xFF; ps_size]);
+    let mut db = Vec::with_capacity(key_size - h_len - 1);
+    db.extend_from_slice(&l_hash);
+    db.extend(std::iter::repeat(0u8).take(ps_len));
+    db.push(0x01);
+    db.extend_from_slice(data);
 
-    result.push(0x00);
+    let mut seed = vec![0u8; h_len];
+    for byte in &mut seed {
+        *byte = rand::random::<u8>();
+    }
 
-    // OID placeholder for SHA-256
-    result.extend_from_slice(&[0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01]);
+    let db_mask = mgf1(&seed, db.len(), hash);
+    let masked_db: Vec<u8> = db.iter().zip(db_mask.iter()).map(|(a, b)| a ^ b).collect();
 
-    result.extend_from_slice(data);
+    let seed_mask = mgf1(&masked_db, h_len, hash);
+    let masked_seed: Vec<u8> = seed.iter().zip(seed_mask.iter()).map(|(a, b)| a ^ b).collect();
+
+    let mut em = Vec::with_capacity(key_size);
+    em.push(0x00);
+    em.extend_from_slice(&masked_seed);
+    em.extend_from_slice(&masked_db);
 
-    Ok(result)
+    Ok(PaddedData { data: em, expected_size: key_size })
 }
 
-/// Remove signature padding (simplified)
-pub fn unpad_for_signature(data: &[u8]) -> Result<Vec<u8>, String> {
-    if data.len() < 11 {
-        return Err("Invalid signature padding: too short".to_string());
+/// RSA-OAEP decoding. The leading zero byte, the recovered `lHash`, and the
+/// `0x01` separator are all checked without branching on their individual
+/// outcomes until a single pass/fail decision at the very end, so an
+/// attacker probing many ciphertexts can't use response timing to build a
+/// Bleichenbacher-style padding oracle against it.
+pub fn unpad_oaep(padded: PaddedData, label: &[u8], hash: OaepHash) -> Result<Vec<u8>, String> {
+    let em = padded.data;
+    let key_size = padded.expected_size;
+    let h_len = hash.output_len();
+
+    if key_size < 2 * h_len + 2 || em.len() != key_size {
+        return Err("Invalid OAEP encoding: bad length".to_string());
     }
 
-    if data[0] != 0x00 || data[1] != 0x01 {
-        return Err("Invalid signature padding: wrong magic bytes".to_string());
+    let masked_seed = &em[1..1 + h_len];
+    let masked_db = &em[1 + h_len..];
+
+    let seed_mask = mgf1(masked_db, h_len, hash);
+    let seed: Vec<u8> = masked_seed.iter().zip(seed_mask.iter()).map(|(a, b)| a ^ b).collect();
+
+    let db_mask = mgf1(&seed, masked_db.len(), hash);
+    let db: Vec<u8> = masked_db.iter().zip(db_mask.iter()).map(|(a, b)| a ^ b).collect();
+
+    let l_hash = hash.digest(label);
+    let mut bad: u8 = em[0]; // Must be 0x00.
+    for (x, y) in db[..h_len].iter().zip(l_hash.iter()) {
+        bad |= x ^ y;
     }
 
-    // Find the end of PS (0xFF bytes)
-    let mut pos = 2;
-    while pos < data.len() && data[pos] == 0xFF {
-        pos += 1;
+    // Walk the whole PS || 0x01 || M region once, tracking whether the
+    // separator has been seen with an arithmetic flag rather than an early
+    // `break`, so the loop always does the same amount of work regardless
+    // of where (or whether) a valid separator appears.
+    let rest = &db[h_len..];
+    let mut looking: u8 = 1;
+    let mut sep_index = rest.len();
+    for (i, &b) in rest.iter().enumerate() {
+        let is_zero = (b == 0) as u8;
+        let is_one = (b == 1) as u8;
+        let take = looking & is_one;
+        sep_index = take as usize * i + (1 - take as usize) * sep_index;
+        bad |= looking & (1 - is_zero) & (1 - is_one);
+        looking &= 1 - is_one;
     }
+    bad |= looking; // Separator was never found.
 
-    // Check for separator
-    if pos >= data.len() || data[pos] != 0x00 {
-        return Err("Invalid signature padding: no separator".to_string());
+    if bad != 0 {
+        return Err("Invalid OAEP encoding".to_string());
     }
 
-    Ok(data[pos + 1..].to_vec())
+    Ok(rest[sep_index + 1..].to_vec())
 }
 
 #[cfg(test)]
@@ -0,0 +1,308 @@
+// RSA Signatures
+// Implements RSASSA-PKCS1-v1_5 and RSASSA-PSS signing and verification.
+// Signing applies the private exponent the same way `decrypt_crt` does;
+// "sign" and "decrypt" are the same modular operation in textbook RSA, just
+// applied to an encoded digest instead of ciphertext.
+
+use super::bigint::{from_bytes, to_bytes, mod_pow};
+use super::decrypt::decrypt_crt;
+use super::keygen::{RsaPrivateKey, RsaPublicKey};
+use super::padding::{mgf1, OaepHash};
+
+/// DER encoding of the DigestInfo `AlgorithmIdentifier` prefix for each
+/// supported hash (RFC 3447 appendix), excluding the digest bytes themselves.
+fn digest_info_prefix(hash: OaepHash) -> &'static [u8] {
+    match hash {
+        OaepHash::Sha1 => &[
+            0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14,
+        ],
+        OaepHash::Sha256 => &[
+            0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0x04,
+            0x20,
+        ],
+        OaepHash::Sha384 => &[
+            0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02, 0x05, 0x00, 0x04,
+            0x30,
+        ],
+        OaepHash::Sha512 => &[
+            0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03, 0x05, 0x00, 0x04,
+            0x40,
+        ],
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+/// Sign `message` with EMSA-PKCS1-v1_5: EM = 0x00 || 0x01 || PS(0xFF) ||
+/// 0x00 || DigestInfo(hash, H(message)), then apply the private exponent.
+pub fn sign_pkcs1_v15(message: &[u8], private_key: &RsaPrivateKey, hash: OaepHash) -> Result<Vec<u8>, String> {
+    let key_size: usize = ((private_key.bit_length() + 7) / 8) as usize;
+    let digest = hash.digest(message);
+    let prefix = digest_info_prefix(hash);
+    let digest_info_len = prefix.len() + digest.len();
+
+    if key_size < digest_info_len + 11 {
+        return Err(format!(
+            "Key too small for a {}-byte DigestInfo: need at least {} bytes, got {}",
+            digest_info_len,
+            digest_info_len + 11,
+            key_size
+        ));
+    }
+    let ps_len = key_size - digest_info_len - 3;
+
+    let mut em = Vec::with_capacity(key_size);
+    em.push(0x00);
+    em.push(0x01);
+    em.extend(std::iter::repeat(0xFFu8).take(ps_len));
+    em.push(0x00);
+    em.extend_from_slice(prefix);
+    em.extend_from_slice(&digest);
+
+    let m = from_bytes(&em);
+    let s = decrypt_crt(&m, private_key)?;
+    let sig_bytes = to_bytes(&s);
+    let mut signature = vec![0u8; key_size];
+    let start = key_size.saturating_sub(sig_bytes.len());
+    signature[start..].copy_from_slice(&sig_bytes);
+    Ok(signature)
+}
+
+/// Verify an EMSA-PKCS1-v1_5 signature by recomputing EM from the public
+/// key and comparing it against the expected encoding.
+pub fn verify_pkcs1_v15(message: &[u8], signature: &[u8], public_key: &RsaPublicKey, hash: OaepHash) -> Result<bool, String> {
+    let key_size: usize = ((public_key.bit_length() + 7) / 8) as usize;
+    if signature.len() != key_size {
+        return Err(format!(
+            "Invalid signature length: expected {} bytes, got {}",
+            key_size,
+            signature.len()
+        ));
+    }
+
+    let s = from_bytes(signature);
+    let m = mod_pow(&s, &public_key.e, &public_key.n);
+    let em_bytes = to_bytes(&m);
+    let mut em = vec![0u8; key_size];
+    let start = key_size.saturating_sub(em_bytes.len());
+    em[start..].copy_from_slice(&em_bytes);
+
+    let digest = hash.digest(message);
+    let prefix = digest_info_prefix(hash);
+    let digest_info_len = prefix.len() + digest.len();
+    if key_size < digest_info_len + 11 {
+        return Ok(false);
+    }
+    let ps_len = key_size - digest_info_len - 3;
+
+    let mut expected = Vec::with_capacity(key_size);
+    expected.push(0x00);
+    expected.push(0x01);
+    expected.extend(std::iter::repeat(0xFFu8).take(ps_len));
+    expected.push(0x00);
+    expected.extend_from_slice(prefix);
+    expected.extend_from_slice(&digest);
+
+    Ok(constant_time_eq(&em, &expected))
+}
+
+/// Sign `message` with RSASSA-PSS (RFC 3447), using MGF1 over `hash` (the
+/// same mask generator as OAEP) and a random salt of `salt_len` bytes.
+pub fn sign_pss(message: &[u8], private_key: &RsaPrivateKey, hash: OaepHash, salt_len: usize) -> Result<Vec<u8>, String> {
+    let key_size: usize = ((private_key.bit_length() + 7) / 8) as usize;
+    let em_bits = private_key.bit_length() as usize - 1;
+    let em_len = (em_bits + 7) / 8;
+    let h_len = hash.output_len();
+
+    if em_len < h_len + salt_len + 2 {
+        return Err("Key too small for PSS with this hash/salt length".to_string());
+    }
+
+    let m_hash = hash.digest(message);
+    let mut salt = vec![0u8; salt_len];
+    for byte in &mut salt {
+        *byte = rand::random::<u8>();
+    }
+
+    // M' = 0x00 * 8 || mHash || salt, H = Hash(M')
+    let mut m_prime = Vec::with_capacity(8 + h_len + salt_len);
+    m_prime.extend_from_slice(&[0u8; 8]);
+    m_prime.extend_from_slice(&m_hash);
+    m_prime.extend_from_slice(&salt);
+    let h = hash.digest(&m_prime);
+
+    // DB = PS(zeros) || 0x01 || salt
+    let ps_len = em_len - salt_len - h_len - 2;
+    let mut db = Vec::with_capacity(em_len - h_len - 1);
+    db.extend(std::iter::repeat(0u8).take(ps_len));
+    db.push(0x01);
+    db.extend_from_slice(&salt);
+
+    let db_mask = mgf1(&h, db.len(), hash);
+    let mut masked_db: Vec<u8> = db.iter().zip(db_mask.iter()).map(|(a, b)| a ^ b).collect();
+
+    // Zero the bits beyond emBits in the leftmost byte.
+    let unused_bits = 8 * em_len - em_bits;
+    if unused_bits > 0 {
+        masked_db[0] &= 0xFFu8 >> unused_bits;
+    }
+
+    let mut em = Vec::with_capacity(em_len + 1);
+    em.extend_from_slice(&masked_db);
+    em.extend_from_slice(&h);
+    em.push(0xBC);
+
+    // emLen can be one byte shorter than the key size when the modulus
+    // bit length isn't a multiple of 8; left-pad before the RSA operation.
+    let mut full_em = vec![0u8; key_size];
+    let start = key_size - em.len();
+    full_em[start..].copy_from_slice(&em);
+
+    let m = from_bytes(&full_em);
+    let s = decrypt_crt(&m, private_key)?;
+    let sig_bytes = to_bytes(&s);
+    let mut signature = vec![0u8; key_size];
+    let start = key_size.saturating_sub(sig_bytes.len());
+    signature[start..].copy_from_slice(&sig_bytes);
+    Ok(signature)
+}
+
+/// Verify an RSASSA-PSS signature against `message` using the public key.
+pub fn verify_pss(message: &[u8], signature: &[u8], public_key: &RsaPublicKey, hash: OaepHash, salt_len: usize) -> Result<bool, String> {
+    let key_size: usize = ((public_key.bit_length() + 7) / 8) as usize;
+    if signature.len() != key_size {
+        return Err(format!(
+            "Invalid signature length: expected {} bytes, got {}",
+            key_size,
+            signature.len()
+        ));
+    }
+
+    let em_bits = public_key.bit_length() as usize - 1;
+    let em_len = (em_bits + 7) / 8;
+    let h_len = hash.output_len();
+    if em_len < h_len + salt_len + 2 {
+        return Ok(false);
+    }
+
+    let s = from_bytes(signature);
+    let m = mod_pow(&s, &public_key.e, &public_key.n);
+    let m_bytes = to_bytes(&m);
+    let mut full_em = vec![0u8; key_size];
+    let start = key_size.saturating_sub(m_bytes.len());
+    full_em[start..].copy_from_slice(&m_bytes);
+    let em = &full_em[key_size - em_len..];
+
+    if em[em_len - 1] != 0xBC {
+        return Ok(false);
+    }
+
+    let masked_db = &em[..em_len - h_len - 1];
+    let h = &em[em_len - h_len - 1..em_len - 1];
+
+    let unused_bits = 8 * em_len - em_bits;
+    if unused_bits > 0 && (masked_db[0] & !(0xFFu8 >> unused_bits)) != 0 {
+        return Ok(false);
+    }
+
+    let db_mask = mgf1(h, masked_db.len(), hash);
+    let mut db: Vec<u8> = masked_db.iter().zip(db_mask.iter()).map(|(a, b)| a ^ b).collect();
+    if unused_bits > 0 {
+        db[0] &= 0xFFu8 >> unused_bits;
+    }
+
+    let ps_len = masked_db.len() - salt_len - 1;
+    if db[..ps_len].iter().any(|&b| b != 0) || db[ps_len] != 0x01 {
+        return Ok(false);
+    }
+    let salt = &db[ps_len + 1..];
+
+    let m_hash = hash.digest(message);
+    let mut m_prime = Vec::with_capacity(8 + h_len + salt_len);
+    m_prime.extend_from_slice(&[0u8; 8]);
+    m_prime.extend_from_slice(&m_hash);
+    m_prime.extend_from_slice(salt);
+    let h_prime = hash.digest(&m_prime);
+
+    Ok(constant_time_eq(h, &h_prime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::keygen::generate_keypair;
+
+    #[test]
+    fn test_pkcs1_v15_roundtrip() {
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let message = b"Hello, signatures!";
+
+        let signature = sign_pkcs1_v15(message, &keypair.private_key, OaepHash::Sha256).unwrap();
+        assert!(verify_pkcs1_v15(message, &signature, &keypair.public_key, OaepHash::Sha256).unwrap());
+    }
+
+    #[test]
+    fn test_pkcs1_v15_rejects_tampered_message() {
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let signature = sign_pkcs1_v15(b"original", &keypair.private_key, OaepHash::Sha256).unwrap();
+        assert!(!verify_pkcs1_v15(b"tampered", &signature, &keypair.public_key, OaepHash::Sha256).unwrap());
+    }
+
+    #[test]
+    fn test_pkcs1_v15_roundtrip_all_hashes() {
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let message = b"Hello, multi-hash signatures!";
+
+        for hash in [OaepHash::Sha1, OaepHash::Sha256, OaepHash::Sha384, OaepHash::Sha512] {
+            let signature = sign_pkcs1_v15(message, &keypair.private_key, hash).unwrap();
+            assert!(verify_pkcs1_v15(message, &signature, &keypair.public_key, hash).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_pkcs1_v15_rejects_wrong_algorithm() {
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let message = b"Hello, signatures!";
+
+        // A signature produced for SHA-256's DigestInfo must not verify
+        // against a verifier expecting a different hash's DigestInfo, even
+        // though both produce a structurally well-formed EM.
+        let signature = sign_pkcs1_v15(message, &keypair.private_key, OaepHash::Sha256).unwrap();
+        assert!(!verify_pkcs1_v15(message, &signature, &keypair.public_key, OaepHash::Sha512).unwrap());
+    }
+
+    #[test]
+    fn test_pss_roundtrip() {
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let message = b"Hello, PSS!";
+
+        let signature = sign_pss(message, &keypair.private_key, OaepHash::Sha256, 32).unwrap();
+        assert!(verify_pss(message, &signature, &keypair.public_key, OaepHash::Sha256, 32).unwrap());
+    }
+
+    #[test]
+    fn test_pss_rejects_tampered_message() {
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let signature = sign_pss(b"original", &keypair.private_key, OaepHash::Sha256, 32).unwrap();
+        assert!(!verify_pss(b"tampered", &signature, &keypair.public_key, OaepHash::Sha256, 32).unwrap());
+    }
+
+    #[test]
+    fn test_pss_nondeterministic() {
+        let keypair = generate_keypair(2048, 65537).unwrap();
+        let message = b"Same message twice";
+
+        let sig1 = sign_pss(message, &keypair.private_key, OaepHash::Sha256, 32).unwrap();
+        let sig2 = sign_pss(message, &keypair.private_key, OaepHash::Sha256, 32).unwrap();
+        assert_ne!(sig1, sig2); // Random salt must vary each call
+    }
+}
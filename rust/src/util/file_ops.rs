@@ -4,6 +4,9 @@
 use std::fs::{File, OpenOptions};
 use std::io::{self, Read, Write};
 use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::rsa::{decrypt_bytes, encrypt_bytes, RsaPrivateKey, RsaPublicKey};
 
 /// Errors that can occur during file operations
 #[derive(Debug)]
@@ -43,15 +46,35 @@ impl From<io::Error> for FileError {
 pub type FileResult<T> = Result<T, FileError>;
 
 /// Configuration for file encryption/decryption
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct FileConfig {
     pub chunk_size: usize,
+    progress_callback: Option<Arc<dyn Fn(f64) + Send + Sync>>,
 }
 
 impl Default for FileConfig {
     fn default() -> Self {
         Self {
             chunk_size: 190,
+            progress_callback: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for FileConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileConfig")
+            .field("chunk_size", &self.chunk_size)
+            .field("progress_callback", &self.progress_callback.is_some())
+            .finish()
+    }
+}
+
+impl FileConfig {
+    /// Report progress to the configured callback, if any, as a percentage.
+    fn report_progress(&self, current: u64, total: u64) {
+        if let Some(callback) = &self.progress_callback {
+            callback(Progress::new(current, total).percent);
         }
     }
 }
@@ -130,10 +153,11 @@ pub fn format_file_size(bytes: u64) -> String {
 
 /// Set progress callback
 impl FileConfig {
-    pub fn with_progress<F>(mut self, _callback: F) -> Self
+    pub fn with_progress<F>(mut self, callback: F) -> Self
     where
-        F: Fn(f64) + 'static,
+        F: Fn(f64) + Send + Sync + 'static,
     {
+        self.progress_callback = Some(Arc::new(callback));
         self
     }
 
@@ -142,3 +166,80 @@ impl FileConfig {
         self
     }
 }
+
+/// Read exactly `buf.len()` bytes, or fewer at EOF. Returns the number of
+/// bytes actually read: `0` at a clean EOF, `buf.len()` on a full read, or
+/// any other value if the stream ended mid-block (a truncated/corrupt file).
+fn read_block(file: &mut File, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        let n = file.read(&mut buf[filled..])?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    Ok(filled)
+}
+
+/// Encrypt `input` to `output`, streaming it in `config.chunk_size`-byte
+/// plaintext blocks (clamped to the key's v1.5 capacity, `k - 11` bytes)
+/// RSA-encrypted one at a time into fixed `k`-byte ciphertext blocks.
+pub fn encrypt_file(input: &PathBuf, output: &PathBuf, public_key: &RsaPublicKey, config: &FileConfig) -> FileResult<()> {
+    let key_bytes: usize = ((public_key.bit_length() + 7) / 8) as usize;
+    let max_chunk = key_bytes.saturating_sub(11).max(1);
+    let chunk_size = config.chunk_size.min(max_chunk).max(1);
+
+    let total = std::fs::metadata(input)?.len();
+    let mut in_file = File::open(input)?;
+    let mut out_file = File::create(output)?;
+
+    let mut buffer = vec![0u8; chunk_size];
+    let mut bytes_done: u64 = 0;
+
+    loop {
+        let filled = read_block(&mut in_file, &mut buffer)?;
+        if filled == 0 {
+            break;
+        }
+
+        let ciphertext = encrypt_bytes(&buffer[..filled], public_key).map_err(FileError::CryptoError)?;
+        out_file.write_all(&ciphertext)?;
+
+        bytes_done += filled as u64;
+        config.report_progress(bytes_done, total);
+    }
+
+    Ok(())
+}
+
+/// Decrypt `input` to `output`, reading fixed `k`-byte RSA ciphertext
+/// blocks and writing back the recovered plaintext pieces in sequence.
+pub fn decrypt_file(input: &PathBuf, output: &PathBuf, private_key: &RsaPrivateKey, config: &FileConfig) -> FileResult<()> {
+    let key_bytes: usize = ((private_key.bit_length() + 7) / 8) as usize;
+
+    let total = std::fs::metadata(input)?.len();
+    let mut in_file = File::open(input)?;
+    let mut out_file = File::create(output)?;
+
+    let mut buffer = vec![0u8; key_bytes];
+    let mut bytes_done: u64 = 0;
+
+    loop {
+        let filled = read_block(&mut in_file, &mut buffer)?;
+        if filled == 0 {
+            break;
+        }
+        if filled != key_bytes {
+            return Err(FileError::InvalidChunk);
+        }
+
+        let plaintext = decrypt_bytes(&buffer, private_key).map_err(FileError::CryptoError)?;
+        out_file.write_all(&plaintext)?;
+
+        bytes_done += filled as u64;
+        config.report_progress(bytes_done, total);
+    }
+
+    Ok(())
+}